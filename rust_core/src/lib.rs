@@ -1,13 +1,18 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
-use std::io::{BufReader, BufWriter};
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::{Instant, SystemTime};
+use bloomfilter::Bloom;
 use ignore::WalkBuilder;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
 
 uniffi::setup_scaffolding!();
 
@@ -23,6 +28,14 @@ pub struct SearchResult {
     pub file_kind: String,
     #[serde(default)]
     pub pretty_date: String,  // Pre-formatted relative date
+    #[serde(default)]
+    pub is_network_path: bool,  // Lives on an SMB/NFS/AFP mount
+    #[serde(default)]
+    pub is_alias: bool,  // macOS Finder alias file; only checked on demand or with `detect_aliases`
+    #[serde(default)]
+    pub checksum: String,  // BLAKE3 hex digest, empty unless computed at index time
+    #[serde(default)]
+    pub page_count: Option<u32>,  // PDFs only, when enriched at index time
 }
 
 // Format relative date in Rust (faster than Swift UI thread)
@@ -44,7 +57,7 @@ fn format_relative_date(timestamp: i64) -> String {
 }
 
 // Cache structure for persistence
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, Default, Clone)]
 struct FileCache {
     last_updated: i64,
     files: Vec<SearchResult>,
@@ -55,28 +68,594 @@ fn cache_path() -> PathBuf {
     PathBuf::from(format!("{}/.fast-finder-cache.json", home))
 }
 
+// Separate cache per file kind, e.g. "PNG Image" -> ~/.fast-finder-cache-png-image.json
+fn kind_cache_path(kind: &str) -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let slug: String = kind
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    PathBuf::from(format!("{}/.fast-finder-cache-{}.json", home, slug))
+}
+
+// Partial index written by `rebuild_index_sampled`
+fn sampled_cache_path() -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(format!("{}/.fast-finder-sample-cache.json", home))
+}
+
+// Snapshot of one directory for change summaries, keyed by a hash of its path
+fn snapshot_cache_path(dir: &str) -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let key = blake3::hash(dir.trim_end_matches('/').as_bytes()).to_hex();
+    PathBuf::from(format!("{}/.fast-finder-snapshot-{}.json", home, &key[..16]))
+}
+
+/// How the on-disk cache is written
+#[derive(uniffi::Record, Clone)]
+pub struct CacheConfig {
+    /// Write the cache zstd-compressed (large home directories can exceed 10 MB of JSON)
+    #[uniffi(default = false)]
+    pub compress_cache: bool,
+}
+
+static CACHE_CONFIG: Mutex<CacheConfig> = Mutex::new(CacheConfig { compress_cache: false });
+
+// Frame header that starts every zstd stream
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Set how subsequent cache writes are encoded. Reads detect the format automatically.
+#[uniffi::export]
+pub fn set_cache_config(config: CacheConfig) {
+    if let Ok(mut lock) = CACHE_CONFIG.lock() {
+        *lock = config;
+    }
+}
+
+#[uniffi::export]
+pub fn get_cache_config() -> CacheConfig {
+    CACHE_CONFIG.lock().map(|c| c.clone()).unwrap_or(CacheConfig { compress_cache: false })
+}
+
+// In-memory copy of the main cache once `warm_cache` has run; kept in sync by `save_cache`
+static WARM_CACHE: Mutex<Option<FileCache>> = Mutex::new(None);
+
+/// Read the on-disk cache into memory so later searches don't re-read it.
+/// Returns the number of entries loaded.
+#[uniffi::export]
+pub fn warm_cache() -> u32 {
+    let cache = load_cache_from(&cache_path());
+    let count = cache.files.len() as u32;
+    set_char_filter(&cache);
+    set_name_acronyms(&cache);
+    if let Ok(mut lock) = WARM_CACHE.lock() {
+        *lock = Some(cache);
+    }
+    count
+}
+
+// Bloom filter over (file name, lowercase character) pairs of the warm cache, used by
+// `search_files` to skip entries missing a query character. Fuzzy matches are
+// subsequences, so every query character has to occur somewhere in the name.
+static NAME_CHAR_FILTER: Mutex<Option<Arc<NameCharFilter>>> = Mutex::new(None);
+
+const CHAR_FILTER_FP_RATE: f64 = 0.001;
+
+struct NameCharFilter {
+    // Exact set of the names the Bloom filter was built from; it says nothing about any other name
+    indexed_names: HashSet<String>,
+    chars: Bloom<u64>,
+}
+
+// Hashed once per entry; each character key is derived from it without rehashing the name
+fn name_hash(file_name: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    file_name.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn char_key(name_hash: u64, c: char) -> u64 {
+    name_hash ^ (c as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+// Distinct lowercase NFC characters, ignoring whitespace
+fn filter_chars(text: &str) -> Vec<char> {
+    let mut chars: Vec<char> = text.nfc().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect();
+    chars.sort_unstable();
+    chars.dedup();
+    chars
+}
+
+fn set_char_filter(cache: &FileCache) {
+    let names: Vec<Vec<char>> = cache.files.iter().map(|f| filter_chars(&f.file_name)).collect();
+    let items: usize = names.iter().map(Vec::len).sum();
+    
+    let mut chars_filter = Bloom::new_for_fp_rate(items.max(1), CHAR_FILTER_FP_RATE);
+    let mut indexed_names = HashSet::with_capacity(cache.files.len());
+    for (file, chars) in cache.files.iter().zip(&names) {
+        let hash = name_hash(&file.file_name);
+        for &c in chars {
+            chars_filter.set(&char_key(hash, c));
+        }
+        indexed_names.insert(file.file_name.clone());
+    }
+    if let Ok(mut lock) = NAME_CHAR_FILTER.lock() {
+        *lock = Some(Arc::new(NameCharFilter { indexed_names, chars: chars_filter }));
+    }
+}
+
+// Acronyms of the warm cache's names (from their NFC form), keyed by raw name, so
+// `score_file_name` doesn't rebuild them for every entry of every search
+static NAME_ACRONYMS: Mutex<Option<Arc<HashMap<String, String>>>> = Mutex::new(None);
+
+fn set_name_acronyms(cache: &FileCache) {
+    let acronyms = cache
+        .files
+        .iter()
+        .map(|f| (f.file_name.clone(), acronym(&normalize_nfc(&f.file_name))))
+        .collect();
+    if let Ok(mut lock) = NAME_ACRONYMS.lock() {
+        *lock = Some(Arc::new(acronyms));
+    }
+}
+
+// The cached acronyms apply only to NFC-normalized matching, like the character filter
+fn cached_acronyms(options: &SearchOptions) -> Option<Arc<HashMap<String, String>>> {
+    NAME_ACRONYMS
+        .lock()
+        .ok()
+        .and_then(|lock| lock.clone())
+        .filter(|_| options.unicode_normalization && options.acronym_bonus != 0)
+}
+
+// False only when the name is a cached one and definitely lacks one of the query's characters
+fn may_match_chars(filter: &NameCharFilter, file_name: &str, query_chars: &[char]) -> bool {
+    if !filter.indexed_names.contains(file_name) {
+        return true;
+    }
+    let hash = name_hash(file_name);
+    query_chars.iter().all(|&c| filter.chars.check(&char_key(hash, c)))
+}
+
 fn load_cache() -> FileCache {
+    if let Some(cache) = WARM_CACHE.lock().ok().and_then(|lock| lock.clone()) {
+        return cache;
+    }
+    load_cache_from(&cache_path())
+}
+
+// An encrypted cache the current key can't decrypt reads as empty;
+// `get_cache_encryption_status` tells the app when that's the case
+fn load_cache_from(path: &Path) -> FileCache {
+    try_load_cache_from(path).unwrap_or_default()
+}
+
+// None only when the file is encrypted and the current key can't decrypt it
+fn try_load_cache_from(path: &Path) -> Option<FileCache> {
+    let Ok(file) = fs::File::open(path) else {
+        return Some(FileCache::default());
+    };
+    let mut reader = BufReader::new(file);
+    let encrypted = reader.fill_buf().map(|buf| buf.starts_with(ENCRYPTED_MAGIC)).unwrap_or(false);
+    if !encrypted {
+        return Some(parse_cache(reader));
+    }
+    
+    let plaintext = fs::read(path).ok().and_then(|bytes| decrypt_cache(&bytes))?;
+    Some(parse_cache(io::Cursor::new(plaintext)))
+}
+
+fn parse_cache<R: BufRead>(mut reader: R) -> FileCache {
+    // Check the magic bytes so caches written either way can be read
+    let compressed = reader.fill_buf().map(|buf| buf.starts_with(&ZSTD_MAGIC)).unwrap_or(false);
+    if compressed {
+        zstd::stream::read::Decoder::with_buffer(reader)
+            .ok()
+            .and_then(|decoder| serde_json::from_reader(decoder).ok())
+            .unwrap_or_default()
+    } else {
+        serde_json::from_reader(reader).unwrap_or_default()
+    }
+}
+
+fn save_cache(cache: &FileCache) -> io::Result<()> {
+    save_cache_to(&cache_path(), cache)?;
+    refresh_warm_cache(cache);
+    Ok(())
+}
+
+fn refresh_warm_cache(cache: &FileCache) {
+    if let Ok(mut lock) = WARM_CACHE.lock() {
+        if lock.is_some() {
+            set_char_filter(cache);
+            set_name_acronyms(cache);
+            *lock = Some(cache.clone());
+        }
+    }
+}
+
+// Refuses to write while the main cache (or `path` itself) is encrypted and no key is set,
+// so a plain copy of the index never replaces or sits next to the encrypted one
+fn save_cache_to(path: &Path, cache: &FileCache) -> io::Result<()> {
+    if is_locked(path) || is_locked(&cache_path()) {
+        return Err(cache_locked_error());
+    }
+    write_cache_file(path, cache)
+}
+
+fn cache_locked_error() -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, "The cache is encrypted; set the encryption key first")
+}
+
+fn write_cache_file(path: &Path, cache: &FileCache) -> io::Result<()> {
+    let compress = get_cache_config().compress_cache;
+    let encrypt = encryption_enabled();
+    write_atomically(path, |writer| {
+        if encrypt {
+            let mut plaintext = Vec::new();
+            if compress {
+                plaintext = zstd::stream::encode_all(serde_json::to_vec(cache)?.as_slice(), 3)?;
+            } else {
+                serde_json::to_writer(&mut plaintext, cache)?;
+            }
+            writer.write_all(&encrypt_cache(&plaintext)?)?;
+        } else if compress {
+            let mut encoder = zstd::stream::write::Encoder::new(writer, 3)?;
+            serde_json::to_writer(&mut encoder, cache)?;
+            encoder.finish()?;
+        } else {
+            serde_json::to_writer(writer, cache)?;
+        }
+        Ok(())
+    })
+}
+
+// Encrypted files are laid out as magic (8 bytes) | salt (16 bytes) | nonce (12 bytes) | AES-256-GCM ciphertext.
+// The magic tells them apart from plain JSON and zstd without guessing from random salt bytes.
+const ENCRYPTED_MAGIC: &[u8; 8] = b"FFCRYPT1";
+const CACHE_SALT_LEN: usize = 16;
+const CACHE_NONCE_LEN: usize = 12;
+const CACHE_KDF_ROUNDS: u32 = 600_000;
+
+struct CacheEncryption {
+    passphrase: String,
+    // Key derived for the most recent salt; PBKDF2 is too slow to rerun on every load
+    derived: Option<([u8; CACHE_SALT_LEN], [u8; 32])>,
+}
+
+// Held in memory only; the app must set it again after each launch before the cache can be read
+static CACHE_ENCRYPTION: Mutex<Option<CacheEncryption>> = Mutex::new(None);
+
+fn encryption_enabled() -> bool {
+    CACHE_ENCRYPTION.lock().map(|lock| lock.is_some()).unwrap_or(false)
+}
+
+fn is_encrypted_file(path: &Path) -> bool {
+    let mut header = [0u8; ENCRYPTED_MAGIC.len()];
+    fs::File::open(path)
+        .and_then(|mut file| read_full(&mut file, &mut header))
+        .is_ok_and(|read| read == header.len() && &header == ENCRYPTED_MAGIC)
+}
+
+// The file is encrypted but no key is set this session, so it can be neither read nor safely rewritten
+fn is_locked(path: &Path) -> bool {
+    !encryption_enabled() && is_encrypted_file(path)
+}
+
+// Small JSON files holding indexed paths (open history) go through the same encryption as the cache
+fn read_private_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+    if bytes.starts_with(ENCRYPTED_MAGIC) {
+        serde_json::from_slice(&decrypt_cache(&bytes)?).ok()
+    } else {
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+fn write_private_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let json = serde_json::to_vec(value)?;
+    let contents = if encryption_enabled() { encrypt_cache(&json)? } else { json };
+    write_atomically(path, |writer| writer.write_all(&contents))
+}
+
+fn cache_key_for_salt(salt: &[u8; CACHE_SALT_LEN]) -> Option<[u8; 32]> {
+    let mut lock = CACHE_ENCRYPTION.lock().ok()?;
+    let encryption = lock.as_mut()?;
+    if let Some((cached_salt, key)) = encryption.derived {
+        if &cached_salt == salt {
+            return Some(key);
+        }
+    }
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(encryption.passphrase.as_bytes(), salt, CACHE_KDF_ROUNDS, &mut key);
+    encryption.derived = Some((*salt, key));
+    Some(key)
+}
+
+fn encrypt_cache(plaintext: &[u8]) -> io::Result<Vec<u8>> {
+    use aes_gcm::aead::{Aead, KeyInit};
+    use rand::RngCore;
+    
+    let no_key = || io::Error::other("cache encryption key is not set");
+    // Reuse the current salt so the key doesn't have to be derived again
+    let salt = CACHE_ENCRYPTION
+        .lock()
+        .ok()
+        .and_then(|lock| lock.as_ref().and_then(|e| e.derived.map(|(salt, _)| salt)))
+        .unwrap_or_else(|| {
+            let mut salt = [0u8; CACHE_SALT_LEN];
+            rand::thread_rng().fill_bytes(&mut salt);
+            salt
+        });
+    let key = cache_key_for_salt(&salt).ok_or_else(no_key)?;
+    let mut nonce = [0u8; CACHE_NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    
+    let cipher = aes_gcm::Aes256Gcm::new(&key.into());
+    let ciphertext = cipher
+        .encrypt(&nonce.into(), plaintext)
+        .map_err(|_| io::Error::other("cache encryption failed"))?;
+    
+    let mut out = Vec::with_capacity(ENCRYPTED_MAGIC.len() + CACHE_SALT_LEN + CACHE_NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(ENCRYPTED_MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+fn decrypt_cache(bytes: &[u8]) -> Option<Vec<u8>> {
+    use aes_gcm::aead::{Aead, KeyInit};
+    
+    let bytes = bytes.strip_prefix(ENCRYPTED_MAGIC)?;
+    if bytes.len() < CACHE_SALT_LEN + CACHE_NONCE_LEN {
+        return None;
+    }
+    let (salt, rest) = bytes.split_at(CACHE_SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(CACHE_NONCE_LEN);
+    let key = cache_key_for_salt(salt.try_into().ok()?)?;
+    
+    let cipher = aes_gcm::Aes256Gcm::new(&key.into());
+    let nonce: [u8; CACHE_NONCE_LEN] = nonce.try_into().ok()?;
+    cipher.decrypt(&nonce.into(), ciphertext).ok()
+}
+
+/// Encrypt the on-disk cache and open history with a key derived from `passphrase`
+/// (PBKDF2-SHA256, AES-256-GCM). The passphrase is kept in memory only, so call this on
+/// every launch before searching. An empty passphrase turns encryption off and rewrites
+/// both files in plain form. While a key is set, rebuilds don't write resume checkpoints.
+#[uniffi::export]
+pub fn set_cache_encryption_key(passphrase: String) -> FileOpResult {
+    let path = cache_path();
+    
+    // Read with the old key, or with the new one if the files were encrypted on a previous launch
+    let previous = try_load_cache_from(&path);
+    let previous_opens: Option<HashMap<String, u32>> = read_private_json(&opens_path());
+    let new_encryption = (!passphrase.is_empty()).then_some(CacheEncryption { passphrase, derived: None });
+    let old_encryption = match CACHE_ENCRYPTION.lock() {
+        Ok(mut lock) => std::mem::replace(&mut *lock, new_encryption),
+        Err(_) => None,
+    };
+    
+    let Some(cache) = previous.or_else(|| try_load_cache_from(&path)) else {
+        // Keep the key that still works rather than one that can't read the cache
+        if let Ok(mut lock) = CACHE_ENCRYPTION.lock() {
+            *lock = old_encryption;
+        }
+        return FileOpResult {
+            success: false,
+            message: "The existing cache could not be decrypted with this passphrase".to_string(),
+            affected_count: 0,
+        };
+    };
+    
+    // Written directly: after turning encryption off the old file still looks locked
+    if path.exists() {
+        if let Err(e) = write_cache_file(&path, &cache) {
+            return FileOpResult {
+                success: false,
+                message: format!("Failed to rewrite cache: {}", e),
+                affected_count: 0,
+            };
+        }
+        refresh_warm_cache(&cache);
+    }
+    if let Some(opens) = previous_opens.or_else(|| read_private_json(&opens_path())) {
+        let _ = write_private_json(&opens_path(), &opens);
+    }
+    FileOpResult {
+        success: true,
+        message: if encryption_enabled() { "Cache encryption enabled" } else { "Cache encryption disabled" }.to_string(),
+        affected_count: cache.files.len() as i32,
+    }
+}
+
+/// Whether cache encryption is in use and whether the cache can currently be read
+#[derive(uniffi::Record, Clone)]
+pub struct CacheEncryptionStatus {
+    pub key_set: bool,          // `set_cache_encryption_key` was called with a passphrase this session
+    pub cache_encrypted: bool,  // The cache file on disk is encrypted
+    pub cache_readable: bool,   // False when the cache is encrypted and the key is missing or wrong
+}
+
+/// Report the cache's encryption state, e.g. to prompt for the passphrase at launch
+/// when the cache is encrypted but no key has been set yet
+#[uniffi::export]
+pub fn get_cache_encryption_status() -> CacheEncryptionStatus {
     let path = cache_path();
+    let cache_encrypted = is_encrypted_file(&path);
+    let cache_readable = !cache_encrypted || fs::read(&path).ok().and_then(|bytes| decrypt_cache(&bytes)).is_some();
+    CacheEncryptionStatus {
+        key_set: encryption_enabled(),
+        cache_encrypted,
+        cache_readable,
+    }
+}
+
+// Write to a temp file next to `path`, then rename over it, so a crash
+// mid-write never leaves a truncated file behind
+fn write_atomically<F>(path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut BufWriter<fs::File>) -> io::Result<()>,
+{
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp_path = path.with_file_name(format!(".{}.tmp-{}-{}", file_name, std::process::id(), nanos));
+    
+    let result = (|| {
+        let mut writer = BufWriter::new(fs::File::create(&tmp_path)?);
+        write(&mut writer)?;
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        
+        match fs::rename(&tmp_path, path) {
+            // Only possible if the directory is itself a mount point; fall back to a plain copy
+            Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+                fs::copy(&tmp_path, path)?;
+                fs::remove_file(&tmp_path)
+            }
+            other => other,
+        }
+    })();
+    
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+// User settings persisted across app restarts
+#[derive(Serialize, Deserialize, Default)]
+struct AppConfig {
+    #[serde(default)]
+    pinned_paths: Vec<String>,
+    #[serde(default)]
+    default_scan_folders: Vec<String>,
+    #[serde(default)]
+    extension_kind_overrides: HashMap<String, String>,
+}
+
+fn config_path() -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(format!("{}/.fast-finder-config.json", home))
+}
+
+fn load_config() -> AppConfig {
+    let path = config_path();
     if let Ok(file) = fs::File::open(&path) {
         let reader = BufReader::new(file);
         serde_json::from_reader(reader).unwrap_or_default()
     } else {
-        FileCache::default()
+        AppConfig::default()
     }
 }
 
-fn save_cache(cache: &FileCache) {
-    let path = cache_path();
-    if let Ok(file) = fs::File::create(&path) {
-        let writer = BufWriter::new(file);
-        let _ = serde_json::to_writer(writer, cache);
+fn save_config(config: &AppConfig) -> io::Result<()> {
+    write_atomically(&config_path(), |writer| {
+        serde_json::to_writer_pretty(writer, config)?;
+        Ok(())
+    })
+}
+
+// User-defined extension -> kind mappings, loaded once and kept in sync with the config
+static EXTENSION_KIND_OVERRIDES: LazyLock<RwLock<HashMap<String, String>>> =
+    LazyLock::new(|| RwLock::new(load_config().extension_kind_overrides));
+
+/// A user-defined file kind for an extension
+#[derive(uniffi::Record, Clone)]
+pub struct ExtensionKindOverride {
+    pub extension: String,
+    pub kind: String,
+}
+
+/// Treat files with `extension` as `kind` (e.g. "notebook" -> "Jupyter Notebook").
+/// An empty `kind` removes the override.
+#[uniffi::export]
+pub fn set_extension_kind_override(extension: String, kind: String) -> FileOpResult {
+    let extension = extension.trim_start_matches('.').to_lowercase();
+    if extension.is_empty() {
+        return FileOpResult {
+            success: false,
+            message: "Extension must not be empty".to_string(),
+            affected_count: 0,
+        };
+    }
+    
+    let mut config = load_config();
+    if kind.is_empty() {
+        config.extension_kind_overrides.remove(&extension);
+    } else {
+        config.extension_kind_overrides.insert(extension.clone(), kind.clone());
+    }
+    
+    if let Err(e) = save_config(&config) {
+        return FileOpResult {
+            success: false,
+            message: format!("Failed to save config: {}", e),
+            affected_count: 0,
+        };
+    }
+    
+    if let Ok(mut overrides) = EXTENSION_KIND_OVERRIDES.write() {
+        *overrides = config.extension_kind_overrides;
+    }
+    
+    FileOpResult {
+        success: true,
+        message: if kind.is_empty() {
+            format!("Removed override for .{}", extension)
+        } else {
+            format!("Files ending in .{} are now '{}'", extension, kind)
+        },
+        affected_count: 1,
     }
 }
 
+/// All user-defined extension overrides, sorted by extension
+#[uniffi::export]
+pub fn get_extension_kind_overrides() -> Vec<ExtensionKindOverride> {
+    let mut overrides: Vec<ExtensionKindOverride> = load_config()
+        .extension_kind_overrides
+        .into_iter()
+        .map(|(extension, kind)| ExtensionKindOverride { extension, kind })
+        .collect();
+    overrides.sort_by(|a, b| a.extension.cmp(&b.extension));
+    overrides
+}
+
+// Directories that macOS presents as a single item, recognized by extension
+fn bundle_kind(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    Some(match ext.as_str() {
+        "app" => "macOS Application",
+        "framework" => "Framework",
+        "xcodeproj" => "Xcode Project",
+        "xcworkspace" => "Xcode Workspace",
+        "playground" => "Swift Playground",
+        "bundle" => "Bundle",
+        "kext" => "Kernel Extension",
+        _ => return None,
+    })
+}
+
 // Helper to get file kind from extension
 fn get_file_kind(path: &std::path::Path, is_folder: bool) -> String {
     if is_folder {
-        return "Folder".to_string();
+        return bundle_kind(path).unwrap_or("Folder").to_string();
+    }
+    
+    // User overrides win over the built-in table
+    if let Some(ext) = path.extension() {
+        if let Ok(overrides) = EXTENSION_KIND_OVERRIDES.read() {
+            if let Some(kind) = overrides.get(&ext.to_string_lossy().to_lowercase()) {
+                return kind.clone();
+            }
+        }
     }
     
     match path.extension().and_then(|e| e.to_str()) {
@@ -115,429 +694,5541 @@ fn get_file_kind(path: &std::path::Path, is_folder: bool) -> String {
     }.to_string()
 }
 
-// Only use mtime and ctime (atime is unreliable on macOS)
-fn get_best_date(metadata: &std::fs::Metadata) -> (i64, &'static str) {
-    let mtime = metadata.modified().ok()
-        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
-        .map(|d| d.as_secs() as i64)
-        .unwrap_or(0);
+// Icon names per file kind: (kind, SF Symbol, Material icon)
+static FILE_KIND_ICONS: &[(&str, &str, &str)] = &[
+    ("Folder", "folder.fill", "folder"),
+    ("PDF Document", "doc.fill", "picture_as_pdf"),
+    ("Word Document", "doc.richtext", "description"),
+    ("Excel Spreadsheet", "tablecells", "table_chart"),
+    ("Presentation", "play.rectangle", "slideshow"),
+    ("Plain Text", "doc.plaintext", "article"),
+    ("Markdown", "doc.text", "article"),
+    ("HTML Document", "globe", "html"),
+    ("CSS Stylesheet", "paintbrush", "css"),
+    ("JavaScript", "chevron.left.forwardslash.chevron.right", "javascript"),
+    ("TypeScript", "chevron.left.forwardslash.chevron.right", "code"),
+    ("JSON", "curlybraces", "data_object"),
+    ("Python Script", "chevron.left.forwardslash.chevron.right", "code"),
+    ("Rust Source", "chevron.left.forwardslash.chevron.right", "code"),
+    ("Swift Source", "swift", "code"),
+    ("Java Source", "chevron.left.forwardslash.chevron.right", "code"),
+    ("Go Source", "chevron.left.forwardslash.chevron.right", "code"),
+    ("C Source", "chevron.left.forwardslash.chevron.right", "code"),
+    ("C++ Source", "chevron.left.forwardslash.chevron.right", "code"),
+    ("JPEG Image", "photo", "image"),
+    ("PNG Image", "photo", "image"),
+    ("GIF Image", "photo", "gif"),
+    ("HEIC Image", "photo", "image"),
+    ("SVG Image", "photo", "image"),
+    ("MP4 Video", "film", "movie"),
+    ("QuickTime Movie", "film", "movie"),
+    ("MP3 Audio", "music.note", "audio_file"),
+    ("WAV Audio", "music.note", "audio_file"),
+    ("ZIP Archive", "doc.zipper", "folder_zip"),
+    ("Disk Image", "externaldrive", "album"),
+    ("Application", "app", "apps"),
+    ("macOS Application", "app", "apps"),
+    ("Framework", "shippingbox", "inventory_2"),
+    ("Xcode Project", "hammer", "code"),
+    ("Xcode Workspace", "hammer", "code"),
+    ("Swift Playground", "swift", "code"),
+    ("Bundle", "shippingbox", "inventory_2"),
+    ("Kernel Extension", "puzzlepiece.extension", "extension"),
+    ("Document", "doc", "insert_drive_file"),
+];
+
+/// Map a result's file kind to a platform icon name
+/// (SF Symbols on Apple platforms, Material icons on Android)
+#[uniffi::export]
+pub fn get_file_icon_name(result: SearchResult) -> String {
+    let entry = FILE_KIND_ICONS.iter().find(|(kind, _, _)| *kind == result.file_kind);
     
-    let ctime = metadata.created().ok()
-        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
-        .map(|d| d.as_secs() as i64)
-        .unwrap_or(0);
+    let (sf_symbol, material) = match entry {
+        Some((_, sf, mat)) => (*sf, *mat),
+        None if result.is_folder => ("folder.fill", "folder"),
+        None => ("doc", "insert_drive_file"),
+    };
     
-    if ctime > mtime {
-        (ctime, "Created")
+    if cfg!(target_os = "android") {
+        material.to_string()
     } else {
-        (mtime, "Modified")
+        sf_symbol.to_string()
     }
 }
 
-/// Load cached index for instant startup
+/// SF Symbol name for a file kind string (e.g. "PDF Document" -> "doc.fill").
+/// Unknown kinds get "doc".
 #[uniffi::export]
-pub fn load_cached_index() -> Vec<SearchResult> {
-    let cache = load_cache();
-    cache.files
+pub fn get_file_kind_icon_sf_symbol(kind: String) -> String {
+    FILE_KIND_ICONS
+        .iter()
+        .find(|(k, _, _)| *k == kind)
+        .map(|(_, sf_symbol, _)| *sf_symbol)
+        .unwrap_or("doc")
+        .to_string()
 }
 
-/// Rebuild the index and save to cache (call in background)
+// Broad category for each kind `get_file_kind` can return; anything else is "Other"
+static FILE_KIND_CATEGORIES: &[(&str, &str)] = &[
+    ("PDF Document", "Document"),
+    ("Word Document", "Document"),
+    ("Excel Spreadsheet", "Document"),
+    ("Presentation", "Document"),
+    ("Plain Text", "Document"),
+    ("Markdown", "Document"),
+    ("HTML Document", "Code"),
+    ("CSS Stylesheet", "Code"),
+    ("JavaScript", "Code"),
+    ("TypeScript", "Code"),
+    ("JSON", "Code"),
+    ("Python Script", "Code"),
+    ("Rust Source", "Code"),
+    ("Swift Source", "Code"),
+    ("Java Source", "Code"),
+    ("Go Source", "Code"),
+    ("C Source", "Code"),
+    ("C++ Source", "Code"),
+    ("Xcode Project", "Code"),
+    ("Xcode Workspace", "Code"),
+    ("Swift Playground", "Code"),
+    ("JPEG Image", "Image"),
+    ("PNG Image", "Image"),
+    ("GIF Image", "Image"),
+    ("HEIC Image", "Image"),
+    ("SVG Image", "Image"),
+    ("MP4 Video", "Video"),
+    ("QuickTime Movie", "Video"),
+    ("MP3 Audio", "Audio"),
+    ("WAV Audio", "Audio"),
+    ("ZIP Archive", "Archive"),
+    ("Disk Image", "Archive"),
+];
+
+/// Broad category for a file kind: "Document", "Image", "Video", "Audio",
+/// "Code", "Archive" or "Other"
 #[uniffi::export]
-pub fn rebuild_index() -> Vec<SearchResult> {
-    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    
-    let scan_folders = vec![
-        format!("{}/Documents", home),
-        format!("{}/Downloads", home),
-        format!("{}/Desktop", home),
-    ];
-    
-    let allowed_extensions: std::collections::HashSet<&str> = [
-        "pdf", "doc", "docx", "txt", "rtf", "md", "pages", "odt",
-        "xls", "xlsx", "csv", "numbers",
-        "ppt", "pptx", "key",
-        "jpg", "jpeg", "png", "gif", "heic", "webp", "svg", "psd", "ai",
-        "mp4", "mov", "avi", "mkv", "webm",
-        "mp3", "wav", "aac", "flac", "m4a",
-        "py", "js", "ts", "rs", "swift", "java", "go", "html", "css", "json",
-        "zip", "tar", "gz", "rar", "7z", "dmg",
-    ].iter().cloned().collect();
-    
-    let results_mutex = Arc::new(Mutex::new(Vec::new()));
-    
-    for folder in scan_folders {
-        if !std::path::Path::new(&folder).exists() {
-            continue;
-        }
-        
-        let results_clone = results_mutex.clone();
-        let allowed_ext = allowed_extensions.clone();
-        
-        let walker = WalkBuilder::new(&folder)
-            .hidden(true)
-            .git_ignore(true)
-            .max_depth(Some(5))
-            .threads(4)
-            .build_parallel();
-        
-        walker.run(move || {
-            let results = results_clone.clone();
-            let allowed_ext = allowed_ext.clone();
-            
-            Box::new(move |entry_result| {
-                if let Ok(entry) = entry_result {
-                    let path = entry.path();
-                    
-                    // Filter by extension
-                    if let Some(ext) = path.extension() {
-                        let ext_lower = ext.to_string_lossy().to_lowercase();
-                        if !allowed_ext.contains(ext_lower.as_str()) {
-                            return ignore::WalkState::Continue;
-                        }
-                    } else if entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
-                        // Skip files without extensions
-                        return ignore::WalkState::Continue;
-                    }
-                    
-                    if let Ok(metadata) = entry.metadata() {
-                        let is_folder = metadata.is_dir();
-                        let (date_value, date_kind) = get_best_date(&metadata);
-                        let name = entry.file_name().to_string_lossy().to_string();
-                        let path_str = path.to_string_lossy().to_string();
-                        let file_kind = get_file_kind(path, is_folder);
-                        
-                        if let Ok(mut lock) = results.lock() {
-                            lock.push(SearchResult {
-                                file_name: name,
-                                file_path: path_str,
-                                file_size: metadata.len(),
-                                is_folder,
-                                score: date_value,
-                                date_value,
-                                date_kind: date_kind.to_string(),
-                                file_kind,
-                                pretty_date: format_relative_date(date_value),
-                            });
-                        }
-                    }
+pub fn get_file_kind_category(kind: String) -> String {
+    FILE_KIND_CATEGORIES
+        .iter()
+        .find(|(k, _)| *k == kind)
+        .map(|(_, category)| *category)
+        .unwrap_or("Other")
+        .to_string()
+}
+
+/// Cached files whose kind falls in `category` (case-insensitive), newest first
+#[uniffi::export]
+pub fn search_by_category(category: String) -> Vec<SearchResult> {
+    let mut results: Vec<SearchResult> = load_cache()
+        .files
+        .into_iter()
+        .filter(|r| get_file_kind_category(r.file_kind.clone()).eq_ignore_ascii_case(category.trim()))
+        .collect();
+    sort_newest_first(&mut results);
+    results
+}
+
+/// Cached entries added or modified after `since_unix`, newest first,
+/// for "what changed since I last looked" views
+#[uniffi::export]
+pub fn get_changelog(since_unix: i64) -> Vec<SearchResult> {
+    let mut results: Vec<SearchResult> = load_cache()
+        .files
+        .into_iter()
+        .filter(|r| r.date_value > since_unix)
+        .collect();
+    sort_newest_first(&mut results);
+    results
+}
+
+// Magic numbers for the common types all sit within the first 16 bytes
+const MIME_SNIFF_LEN: usize = 16;
+
+/// MIME type of in-memory content (e.g. a clipboard or drag payload), sniffed from
+/// its leading magic bytes. "application/octet-stream" when unrecognized.
+#[uniffi::export]
+pub fn get_mime_type_from_bytes(bytes: Vec<u8>) -> String {
+    let head = &bytes[..bytes.len().min(MIME_SNIFF_LEN)];
+    infer::Infer::new()
+        .get(head)
+        .map(|kind| kind.mime_type())
+        .unwrap_or("application/octet-stream")
+        .to_string()
+}
+
+// Only use mtime and ctime (atime is unreliable on macOS)
+fn get_best_date(metadata: &FileMetadata) -> (i64, &'static str) {
+    if metadata.created > metadata.modified {
+        (metadata.created, "Created")
+    } else {
+        (metadata.modified, "Modified")
+    }
+}
+
+/// A `SearchResult` field to sort by
+#[derive(uniffi::Enum, Clone, Copy, Debug, PartialEq)]
+pub enum SortField {
+    FileName,
+    FilePath,
+    FileSize,
+    DateValue,
+    FileKind,
+    Score,
+}
+
+// Secondary sort key so results with equal primary keys keep a stable order
+static TIEBREAKER_FIELD: Mutex<SortField> = Mutex::new(SortField::FileName);
+
+/// Choose the field used to break ties when sorting index and search results
+/// (defaults to file name, ascending)
+#[uniffi::export]
+pub fn set_tiebreaker_field(field: SortField) {
+    if let Ok(mut lock) = TIEBREAKER_FIELD.lock() {
+        *lock = field;
+    }
+}
+
+fn tiebreaker_field() -> SortField {
+    TIEBREAKER_FIELD.lock().map(|f| *f).unwrap_or(SortField::FileName)
+}
+
+// Ascending comparison of two results by a single field
+fn compare_by_field(a: &SearchResult, b: &SearchResult, field: SortField) -> std::cmp::Ordering {
+    match field {
+        SortField::FileName => a.file_name.cmp(&b.file_name),
+        SortField::FilePath => a.file_path.cmp(&b.file_path),
+        SortField::FileSize => a.file_size.cmp(&b.file_size),
+        SortField::DateValue => a.date_value.cmp(&b.date_value),
+        SortField::FileKind => a.file_kind.cmp(&b.file_kind),
+        SortField::Score => a.score.cmp(&b.score),
+    }
+}
+
+// Index order: newest first, ties broken by the configured field
+fn sort_newest_first(results: &mut [SearchResult]) {
+    let tiebreaker = tiebreaker_field();
+    results.sort_by(|a, b| {
+        b.date_value.cmp(&a.date_value).then_with(|| compare_by_field(a, b, tiebreaker))
+    });
+}
+
+// ============== FILESYSTEM ABSTRACTION ==============
+
+/// The subset of file metadata the indexer needs, independent of `std::fs`
+#[derive(Clone, Debug, Default)]
+struct FileMetadata {
+    len: u64,
+    is_dir: bool,
+    modified: i64,
+    created: i64,
+}
+
+impl FileMetadata {
+    fn from_std(metadata: &fs::Metadata) -> Self {
+        let to_secs = |t: std::io::Result<SystemTime>| {
+            t.ok()
+                .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0)
+        };
+        
+        FileMetadata {
+            len: metadata.len(),
+            is_dir: metadata.is_dir(),
+            modified: to_secs(metadata.modified()),
+            created: to_secs(metadata.created()),
+        }
+    }
+}
+
+// Walking and stat behind a trait, so `walk_roots` can index a `MockFileSystem` in tests
+
+/// A path reached while walking a `TestableFileSystem`
+#[derive(Clone, Debug)]
+struct WalkEntry {
+    path: PathBuf,
+    // From the entry itself, so an unfollowed symlink is neither a file nor a folder
+    is_file: bool,
+    is_dir: bool,
+}
+
+// A failed entry carries the path it failed at (empty when unknown) and why
+type EntryResult = Result<WalkEntry, IndexError>;
+
+/// Walking and stat operations the indexer goes through
+trait TestableFileSystem: Sync {
+    /// Call `visit` for `root` and every entry below it that `config` lets through,
+    /// possibly from several threads (hence a callback rather than an iterator).
+    /// Returning false from `visit` skips a folder's contents.
+    fn walk(&self, root: &Path, config: &WalkConfig, visit: &(dyn Fn(EntryResult) -> bool + Sync));
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata>;
+}
+
+/// The disk, walked in parallel with `index_walk_builder`'s rules
+struct RealFileSystem;
+
+impl TestableFileSystem for RealFileSystem {
+    fn walk(&self, root: &Path, config: &WalkConfig, visit: &(dyn Fn(EntryResult) -> bool + Sync)) {
+        index_walk_builder(root, config).threads(4).build_parallel().run(|| {
+            Box::new(move |entry_result| {
+                let entry_result = entry_result
+                    .map(|entry| WalkEntry {
+                        path: entry.path().to_path_buf(),
+                        is_file: entry.file_type().is_some_and(|ft| ft.is_file()),
+                        is_dir: entry.file_type().is_some_and(|ft| ft.is_dir()),
+                    })
+                    .map_err(|e| IndexError { path: walk_error_path(&e), message: e.to_string() });
+                if visit(entry_result) {
+                    ignore::WalkState::Continue
+                } else {
+                    ignore::WalkState::Skip
                 }
-                ignore::WalkState::Continue
             })
         });
     }
     
-    let mut final_results = results_mutex.lock().unwrap().clone();
-    final_results.sort_by(|a, b| b.date_value.cmp(&a.date_value));
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        // A broken symlink still has metadata of its own
+        fs::metadata(path)
+            .or_else(|_| fs::symlink_metadata(path))
+            .map(|m| FileMetadata::from_std(&m))
+    }
+}
+
+/// In-memory filesystem for deterministic tests
+#[cfg(test)]
+struct MockFileSystem {
+    entries: Vec<(PathBuf, FileMetadata)>,
+}
+
+#[cfg(test)]
+impl MockFileSystem {
+    fn new(mut entries: Vec<(PathBuf, FileMetadata)>) -> Self {
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        MockFileSystem { entries }
+    }
+}
+
+#[cfg(test)]
+impl TestableFileSystem for MockFileSystem {
+    // Only `max_depth` applies; the ignore, hidden-folder and exclusion rules are the real walker's
+    fn walk(&self, root: &Path, config: &WalkConfig, visit: &(dyn Fn(EntryResult) -> bool + Sync)) {
+        let mut skipped: Vec<&Path> = Vec::new();
+        for (path, metadata) in &self.entries {
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            if relative.components().count() > config.max_depth as usize || skipped.iter().any(|dir| path.starts_with(dir)) {
+                continue;
+            }
+            let entry = WalkEntry { path: path.clone(), is_file: !metadata.is_dir, is_dir: metadata.is_dir };
+            if !visit(Ok(entry)) {
+                skipped.push(path);
+            }
+        }
+    }
+    
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.entries
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, m)| m.clone())
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+}
+
+// Extensions that rebuild_index includes in the index
+fn indexed_extensions() -> HashSet<&'static str> {
+    [
+        "pdf", "doc", "docx", "txt", "rtf", "md", "pages", "odt",
+        "xls", "xlsx", "csv", "numbers",
+        "ppt", "pptx", "key",
+        "jpg", "jpeg", "png", "gif", "heic", "webp", "svg", "psd", "ai",
+        "mp4", "mov", "avi", "mkv", "webm",
+        "mp3", "wav", "aac", "flac", "m4a",
+        "py", "js", "ts", "rs", "swift", "java", "go", "html", "css", "json",
+        "zip", "tar", "gz", "rar", "7z", "dmg",
+    ].iter().cloned().collect()
+}
+
+// Files need an allowed extension; folders are kept unless their extension is filtered out
+fn is_indexable(path: &Path, is_file: bool, allowed_ext: &HashSet<&str>) -> bool {
+    match path.extension() {
+        Some(ext) => allowed_ext.contains(ext.to_string_lossy().to_lowercase().as_str()),
+        None => !is_file,
+    }
+}
+
+// Build the index entry for a path; score defaults to the date so newest sorts first
+fn build_result(path: &Path, metadata: &FileMetadata) -> SearchResult {
+    let (date_value, date_kind) = get_best_date(metadata);
+    let path_str = path.to_string_lossy().to_string();
+    let name = path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path_str.clone());
     
-    // Save to cache
-    let now = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64;
+    SearchResult {
+        file_name: name,
+        file_kind: get_file_kind(path, metadata.is_dir),
+        file_path: path_str,
+        file_size: metadata.len,
+        is_folder: metadata.is_dir,
+        score: date_value,
+        date_value,
+        date_kind: date_kind.to_string(),
+        pretty_date: format_relative_date(date_value),
+        is_network_path: false,
+        is_alias: false,
+        checksum: String::new(),
+        page_count: None,
+    }
+}
+
+// ============== IGNORE RULES ==============
+
+/// Whether `path` would be excluded by the rules in a specific `.gitignore`
+/// file (patterns are relative to the folder containing it), without walking.
+/// Returns false if the ignore file can't be read or `path` lies outside its folder.
+#[uniffi::export]
+pub fn path_matches_gitignore(path: String, ignore_file: String) -> bool {
+    use ignore::gitignore::GitignoreBuilder;
     
-    let cache = FileCache {
-        last_updated: now,
-        files: final_results.clone(),
+    let ignore_path = Path::new(&ignore_file);
+    let root = ignore_path.parent().unwrap_or(Path::new("."));
+    
+    let mut builder = GitignoreBuilder::new(root);
+    if builder.add(ignore_path).is_some() {
+        return false;
+    }
+    let gitignore = match builder.build() {
+        Ok(g) => g,
+        Err(_) => return false,
     };
-    save_cache(&cache);
     
-    final_results
+    let target = Path::new(&path);
+    // Matching panics on absolute paths outside the root
+    if target.is_absolute() && !target.starts_with(root) {
+        return false;
+    }
+    
+    gitignore
+        .matched_path_or_any_parents(target, target.is_dir())
+        .is_ignore()
 }
 
-#[uniffi::export]
-pub fn search_files(query: String) -> Vec<SearchResult> {
-    if query.trim().is_empty() {
+// ============== NETWORK VOLUMES ==============
+
+// File system types that are backed by a remote server
+#[cfg(target_os = "linux")]
+const NETWORK_FS_TYPES: &[&str] = &[
+    "nfs", "nfs4", "smbfs", "smb3", "cifs", "afpfs", "webdav", "davfs", "fuse.sshfs",
+];
+
+/// Mount points of network file systems (SMB/NFS/AFP/...), read once per walk
+#[cfg(target_os = "macos")]
+fn network_mount_points() -> Vec<PathBuf> {
+    use std::ffi::CStr;
+    
+    let mut mounts: *mut libc::statfs = std::ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut mounts, libc::MNT_NOWAIT) };
+    if count <= 0 || mounts.is_null() {
         return Vec::new();
     }
+    
+    let mounts = unsafe { std::slice::from_raw_parts(mounts, count as usize) };
+    mounts
+        .iter()
+        .filter(|m| (m.f_flags as u64) & (libc::MNT_LOCAL as u64) == 0)
+        .map(|m| {
+            let on_name = unsafe { CStr::from_ptr(m.f_mntonname.as_ptr()) };
+            PathBuf::from(on_name.to_string_lossy().to_string())
+        })
+        .collect()
+}
 
-    let root_path = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    let matcher = Arc::new(SkimMatcherV2::default().smart_case());
-    let results = Arc::new(Mutex::new(Vec::new()));
+#[cfg(target_os = "linux")]
+fn network_mount_points() -> Vec<PathBuf> {
+    let mounts = fs::read_to_string("/proc/mounts").unwrap_or_default();
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?;
+            let fs_type = fields.next()?;
+            NETWORK_FS_TYPES
+                .contains(&fs_type)
+                .then(|| PathBuf::from(unescape_mount_path(mount_point)))
+        })
+        .collect()
+}
 
-    let walker = WalkBuilder::new(root_path)
-        .hidden(true)
-        .git_ignore(true)
-        .max_depth(Some(6))
-        .threads(4)
-        .build_parallel();
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn network_mount_points() -> Vec<PathBuf> {
+    Vec::new()
+}
 
-    let results_clone = results.clone();
-    let query_clone = query.clone();
+// /proc/mounts escapes spaces, tabs and backslashes as octal (e.g. `\040`)
+#[cfg(target_os = "linux")]
+fn unescape_mount_path(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let is_octal = i + 3 < bytes.len()
+            && bytes[i + 1..i + 4].iter().all(|b| (b'0'..=b'7').contains(b));
+        if bytes[i] == b'\\' && is_octal {
+            let octal = std::str::from_utf8(&bytes[i + 1..i + 4]).unwrap_or("0");
+            out.push(u8::from_str_radix(octal, 8).unwrap_or(b'?'));
+            i += 4;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
 
-    walker.run(move || {
-        let results = results_clone.clone();
-        let query = query_clone.clone();
-        let matcher = matcher.clone();
-        
-        Box::new(move |entry_result| {
-            if let Ok(entry) = entry_result {
-                let file_name = entry.file_name().to_string_lossy();
-                
-                if let Some(score) = matcher.fuzzy_match(&file_name, &query) {
-                    let is_folder = entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false);
-                    let path = entry.path();
-                    let path_str = path.to_string_lossy().to_string();
-                    
-                    let (size, date_value, date_kind) = if let Ok(metadata) = entry.metadata() {
-                        let (dv, dk) = get_best_date(&metadata);
-                        (metadata.len(), dv, dk)
-                    } else {
-                        (0, 0, "Unknown")
-                    };
-                    
-                    let file_kind = get_file_kind(path, is_folder);
+// True when `path` is at or below one of the network mount points
+fn is_network_path(path: &Path, network_mounts: &[PathBuf]) -> bool {
+    network_mounts.iter().any(|mount| path.starts_with(mount))
+}
 
-                    if let Ok(mut lock) = results.lock() {
-                        if lock.len() < 2000 {
-                            lock.push(SearchResult {
-                                file_name: file_name.to_string(),
-                                file_path: path_str,
-                                file_size: size,
-                                is_folder,
-                                score,
-                                date_value,
-                                date_kind: date_kind.to_string(),
-                                file_kind,
-                                pretty_date: format_relative_date(date_value),
-                            });
-                        } else {
-                            return ignore::WalkState::Quit;
-                        }
-                    }
-                }
-            }
-            ignore::WalkState::Continue
-        })
-    });
+// Stat a single path into a result (score = date), or None if it can't be read
+fn stat_result(path: &Path) -> Option<SearchResult> {
+    let metadata = fs::metadata(path).ok()?;
+    let mut result = build_result(path, &FileMetadata::from_std(&metadata));
+    result.is_network_path = is_network_path(path, &network_mount_points());
+    result.is_alias = !result.is_folder && is_finder_alias(path);
+    Some(result)
+}
 
-    let mut final_results = results.lock().unwrap().clone();
-    final_results.sort_by(|a, b| b.score.cmp(&a.score));
-    final_results.truncate(50);
+/// Load cached index for instant startup
+#[uniffi::export]
+pub fn load_cached_index() -> Vec<SearchResult> {
+    let cache = load_cache();
+    cache.files
+}
 
-    final_results
+/// Every cached path, one per entry, for piping into `fzf`, `grep` or `awk`
+#[uniffi::export]
+pub fn get_cache_as_flat_file_list() -> Vec<String> {
+    load_cache().files.into_iter().map(|f| f.file_path).collect()
 }
 
+/// Cached paths whose file kind is one of `kinds` (e.g. "PDF Document")
 #[uniffi::export]
-pub fn get_recent_files() -> Vec<SearchResult> {
-    // First try to return cached data for instant response
-    let cache = load_cache();
-    let now = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64;
-    
-    // Filter to files modified in last 7 days
-    let week_ago = now - (60 * 60 * 24 * 7);
-    
-    let mut recent: Vec<SearchResult> = cache.files
+pub fn get_cache_as_flat_file_list_filtered(kinds: Vec<String>) -> Vec<String> {
+    load_cache()
+        .files
         .into_iter()
-        .filter(|f| f.date_value > week_ago)
-        .collect();
-    
-    recent.sort_by(|a, b| b.date_value.cmp(&a.date_value));
-    recent.truncate(50);
-    
-    recent
+        .filter(|f| kinds.contains(&f.file_kind))
+        .map(|f| f.file_path)
+        .collect()
 }
 
-// ============== FILE OPERATIONS ==============
+/// Options controlling how `rebuild_index_with_config` walks the scan folders
+#[derive(uniffi::Record, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WalkConfig {
+    #[uniffi(default = 5)]
+    pub max_depth: u32,
+    /// Don't descend into SMB/NFS/AFP mounts, where every stat is a round trip
+    #[uniffi(default = false)]
+    pub skip_network_volumes: bool,
+    /// Descend into symlinked folders. Symlink cycles could otherwise loop forever,
+    /// but the walker detects them and reports an error for that entry instead.
+    #[uniffi(default = false)]
+    pub follow_symlinks: bool,
+    /// Never descend into dot-directories (`.git`, `.cache`, ...) below a scan root
+    #[uniffi(default = true)]
+    pub skip_hidden_dirs: bool,
+    /// Hash every indexed file (BLAKE3) so duplicate detection can run from the cache
+    #[uniffi(default = false)]
+    pub compute_checksums_during_index: bool,
+    /// Read the page count of every indexed PDF into `page_count`
+    #[uniffi(default = false)]
+    pub enrich_pdf_metadata: bool,
+    /// Absolute paths to leave out, along with everything below them
+    #[uniffi(default = [])]
+    pub exclude_paths: Vec<String>,
+    /// Read every indexed file's Finder info to fill in `is_alias` (one extra xattr read per file)
+    #[uniffi(default = false)]
+    pub detect_aliases: bool,
+}
 
-/// Result type for file operations
-#[derive(uniffi::Record, Clone)]
-pub struct FileOpResult {
-    pub success: bool,
-    pub message: String,
-    pub affected_count: i32,
+impl Default for WalkConfig {
+    fn default() -> Self {
+        WalkConfig {
+            max_depth: 5,
+            skip_network_volumes: false,
+            follow_symlinks: false,
+            skip_hidden_dirs: true,
+            compute_checksums_during_index: false,
+            enrich_pdf_metadata: false,
+            exclude_paths: Vec::new(),
+            detect_aliases: false,
+        }
+    }
 }
 
-/// Move files to a destination folder
+/// Why a stored `WalkConfig` couldn't be restored
+#[derive(uniffi::Error, Debug, Clone)]
+pub enum WalkConfigError {
+    InvalidJson(String),
+    InvalidValue(String),
+}
+
+impl std::fmt::Display for WalkConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WalkConfigError::InvalidJson(message) => write!(f, "Invalid JSON: {}", message),
+            WalkConfigError::InvalidValue(message) => write!(f, "Invalid value: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for WalkConfigError {}
+
+/// Serialize a `WalkConfig` so the app can store it as a single preference
 #[uniffi::export]
-pub fn move_files(source_paths: Vec<String>, destination: String) -> FileOpResult {
-    let dest_path = std::path::Path::new(&destination);
+pub fn walk_config_to_json(config: WalkConfig) -> String {
+    serde_json::to_string(&config).unwrap_or_default()
+}
+
+/// Restore a `WalkConfig` saved by `walk_config_to_json`. Missing fields take their defaults.
+#[uniffi::export]
+pub fn walk_config_from_json(json: String) -> Result<WalkConfig, WalkConfigError> {
+    let config: WalkConfig =
+        serde_json::from_str(&json).map_err(|e| WalkConfigError::InvalidJson(e.to_string()))?;
     
-    // Create destination if it doesn't exist
-    if !dest_path.exists() {
-        if let Err(e) = fs::create_dir_all(dest_path) {
-            return FileOpResult {
-                success: false,
-                message: format!("Failed to create destination: {}", e),
-                affected_count: 0,
-            };
+    if config.max_depth == 0 {
+        return Err(WalkConfigError::InvalidValue("max_depth must be greater than 0".to_string()));
+    }
+    if let Some(path) = config.exclude_paths.iter().find(|p| !Path::new(p).is_absolute()) {
+        return Err(WalkConfigError::InvalidValue(format!("exclude_paths must be absolute: {}", path)));
+    }
+    Ok(config)
+}
+
+/// Rebuild the index and save to cache (call in background)
+#[uniffi::export]
+pub fn rebuild_index() -> Vec<SearchResult> {
+    rebuild_index_with_stats().results
+}
+
+/// `rebuild_index` as a future: the walk runs on Tokio's blocking thread pool.
+/// If that task panics or is cancelled, the result is empty and the reason is
+/// reported by `get_last_index_errors`.
+#[cfg(feature = "tokio")]
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn rebuild_index_async() -> Vec<SearchResult> {
+    match tokio::task::spawn_blocking(rebuild_index).await {
+        Ok(results) => results,
+        Err(e) => {
+            set_last_index_errors(vec![IndexError {
+                path: String::new(),
+                message: format!("Background rebuild failed: {}", e),
+            }]);
+            Vec::new()
         }
     }
-    
-    let mut moved = 0;
+}
+
+/// Rebuild the index with custom walk options and save to cache
+#[uniffi::export]
+pub fn rebuild_index_with_config(config: WalkConfig) -> Vec<SearchResult> {
+    rebuild_and_save(&get_default_scan_folders(), &config).results
+}
+
+/// Rebuild the index from specific folders instead of the defaults and save to cache
+#[uniffi::export]
+pub fn rebuild_index_with_paths(paths: Vec<String>) -> Vec<SearchResult> {
+    rebuild_and_save(&paths, &WalkConfig::default()).results
+}
+
+/// Per-root scan settings for `rebuild_index_with_root_configs`
+#[derive(uniffi::Record, Clone)]
+pub struct RootScanConfig {
+    pub root: String,
+    /// Folders to skip, relative to `root` (e.g. "Archive" or "Projects/old")
+    pub exclude_relative_paths: Vec<String>,
+}
+
+/// Rebuild the index from the given roots, each with its own exclusions, and save to cache.
+/// Excluding "Archive" under ~/Documents leaves ~/Desktop/Archive indexed.
+#[uniffi::export]
+pub fn rebuild_index_with_root_configs(configs: Vec<RootScanConfig>) -> Vec<SearchResult> {
+    let mut results = Vec::new();
     let mut errors = Vec::new();
+    for root_config in &configs {
+        let root = Path::new(&root_config.root);
+        let config = WalkConfig {
+            exclude_paths: root_config
+                .exclude_relative_paths
+                .iter()
+                .map(|relative| root.join(relative.trim_matches('/')).to_string_lossy().to_string())
+                .collect(),
+            ..WalkConfig::default()
+        };
+        let (root_results, stats) = walk_roots(std::slice::from_ref(&root_config.root), &config, None, None);
+        results.extend(root_results);
+        errors.extend(stats.errors);
+    }
     
-    for src in &source_paths {
+    sort_newest_first(&mut results);
+    if let Err(e) = save_index(&results) {
+        errors.push(save_index_error(e));
+    }
+    set_last_index_errors(errors);
+    results
+}
+
+/// A path that couldn't be indexed and why
+#[derive(uniffi::Record, Clone)]
+pub struct IndexError {
+    pub path: String,
+    pub message: String,
+}
+
+/// Counters describing one index rebuild
+#[derive(uniffi::Record, Clone, Default)]
+pub struct RebuildStats {
+    pub files_scanned: u32,
+    pub files_indexed: u32,
+    pub errors: Vec<IndexError>,
+    pub duration_ms: u64,
+}
+
+// Errors from the most recent rebuild, for `get_last_index_errors`
+static LAST_INDEX_ERRORS: Mutex<Vec<IndexError>> = Mutex::new(Vec::new());
+
+/// Paths the most recent rebuild couldn't read (permission denied, vanished, ...).
+/// Only rebuilds that replace the main index count; partial and sampled ones don't.
+#[uniffi::export]
+pub fn get_last_index_errors() -> Vec<IndexError> {
+    LAST_INDEX_ERRORS.lock().map(|e| e.clone()).unwrap_or_default()
+}
+
+fn set_last_index_errors(errors: Vec<IndexError>) {
+    if let Ok(mut last) = LAST_INDEX_ERRORS.lock() {
+        *last = errors;
+    }
+}
+
+/// A rebuilt index together with how the rebuild went
+#[derive(uniffi::Record, Clone)]
+pub struct RebuildOutcome {
+    pub results: Vec<SearchResult>,
+    pub stats: RebuildStats,
+}
+
+/// Rebuild the index like `rebuild_index`, also reporting scan counts and timing
+#[uniffi::export]
+pub fn rebuild_index_with_stats() -> RebuildOutcome {
+    rebuild_and_save(&get_default_scan_folders(), &WalkConfig::default())
+}
+
+// Full rebuilds checkpoint their progress so an interrupted one can resume.
+// Checkpoints are plain text, so they're skipped while the cache is encrypted or locked.
+fn rebuild_and_save(roots: &[String], config: &WalkConfig) -> RebuildOutcome {
+    let checkpoint = checkpoint_path();
+    let use_checkpoint = !encryption_enabled() && !is_encrypted_file(&cache_path());
+    if !use_checkpoint {
+        let _ = fs::remove_file(&checkpoint);
+    }
+    let (results, mut stats) = walk_roots(roots, config, None, use_checkpoint.then_some(checkpoint.as_path()));
+    if let Err(e) = save_index(&results) {
+        stats.errors.push(save_index_error(e));
+    }
+    let _ = fs::remove_file(&checkpoint);
+    set_last_index_errors(stats.errors.clone());
+    RebuildOutcome { results, stats }
+}
+
+// A checkpoint is JSON lines: this header, then one indexed entry per line,
+// appended as the walk goes so writing it stays linear in the number of files
+#[derive(Serialize, Deserialize)]
+struct CheckpointHeader {
+    key: String,
+}
+
+fn checkpoint_path() -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(format!("{}/.fast-finder-checkpoint.jsonl", home))
+}
+
+// Identifies a rebuild by everything that decides which entries it indexes and what
+// they contain, so a checkpoint is only resumed by an identical rebuild
+fn checkpoint_key(roots: &[String], config: &WalkConfig) -> String {
+    let inputs = serde_json::json!({
+        "roots": roots,
+        "config": config,
+        "exclusions": list_system_exclusions(),
+        "noise": noise_patterns(),
+    });
+    blake3::hash(inputs.to_string().as_bytes()).to_hex().to_string()
+}
+
+// Entries from a checkpoint left by an interrupted rebuild with the same key, by path
+fn load_checkpoint(path: &Path, key: &str) -> HashMap<String, SearchResult> {
+    let Ok(file) = fs::File::open(path) else {
+        return HashMap::new();
+    };
+    let mut lines = BufReader::new(file).lines();
+    let header: Option<CheckpointHeader> =
+        lines.next().and_then(|line| line.ok()).and_then(|line| serde_json::from_str(&line).ok());
+    if header.is_none_or(|header| header.key != key) {
+        return HashMap::new();
+    }
+    // A crash can leave the last line half-written; it fails to parse and is dropped
+    lines
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<SearchResult>(&line).ok())
+        .map(|f| (f.file_path.clone(), f))
+        .collect()
+}
+
+// Appends entries to a new checkpoint file, shared between walker threads
+struct CheckpointWriter {
+    writer: Mutex<BufWriter<fs::File>>,
+}
+
+impl CheckpointWriter {
+    fn create(path: &Path, key: &str) -> io::Result<Self> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        serde_json::to_writer(&mut writer, &CheckpointHeader { key: key.to_string() })?;
+        writer.write_all(b"\n")?;
+        Ok(CheckpointWriter { writer: Mutex::new(writer) })
+    }
+    
+    fn append(&self, result: &SearchResult) {
+        let Ok(line) = serde_json::to_vec(result) else {
+            return;
+        };
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.write_all(&line).and_then(|_| writer.write_all(b"\n"));
+        }
+    }
+}
+
+/// Quickly build a partial index keeping roughly `sample_fraction` (0.0-1.0) of
+/// the entries, for instant startup on huge home directories. Saved to its own
+/// cache (see `load_sampled_index`) so it never replaces a full index; call
+/// `rebuild_index` afterwards for full coverage. Nothing is saved while the
+/// encrypted cache is locked (see `get_cache_encryption_status`).
+#[uniffi::export]
+pub fn rebuild_index_sampled(sample_fraction: f64) -> Vec<SearchResult> {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use std::cell::RefCell;
+    
+    thread_local! {
+        static SAMPLER: RefCell<SmallRng> = RefCell::new(SmallRng::from_entropy());
+    }
+    
+    let fraction = sample_fraction.clamp(0.0, 1.0);
+    let filter: EntryFilter = Arc::new(move |_path: &Path, _is_folder: bool| {
+        SAMPLER.with(|rng| rng.borrow_mut().gen::<f64>() < fraction)
+    });
+    
+    let (results, _) = walk_roots(&get_default_scan_folders(), &WalkConfig::default(), Some(filter), None);
+    let _ = save_cache_to(&sampled_cache_path(), &FileCache {
+        last_updated: unix_now(),
+        files: results.clone(),
+    });
+    results
+}
+
+/// Load the partial index saved by `rebuild_index_sampled`
+#[uniffi::export]
+pub fn load_sampled_index() -> Vec<SearchResult> {
+    load_cache_from(&sampled_cache_path()).files
+}
+
+/// Build an index containing only one file kind (e.g. "PNG Image") from the
+/// default scan folders. Saved to its own cache so it doesn't replace the full index,
+/// unless the encrypted cache is locked.
+#[uniffi::export]
+pub fn rebuild_index_for_kind(kind: String) -> Vec<SearchResult> {
+    let target_kind = kind.clone();
+    let filter: EntryFilter = Arc::new(move |path: &Path, is_folder: bool| get_file_kind(path, is_folder) == target_kind);
+    
+    let (results, _) = walk_roots(&get_default_scan_folders(), &WalkConfig::default(), Some(filter), None);
+    let _ = save_cache_to(&kind_cache_path(&kind), &FileCache {
+        last_updated: unix_now(),
+        files: results.clone(),
+    });
+    results
+}
+
+/// Load the partial index saved by `rebuild_index_for_kind`
+#[uniffi::export]
+pub fn load_cached_index_for_kind(kind: String) -> Vec<SearchResult> {
+    load_cache_from(&kind_cache_path(&kind)).files
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+// Replace the on-disk cache with a freshly built index
+fn save_index(results: &[SearchResult]) -> io::Result<()> {
+    let cache = FileCache {
+        last_updated: unix_now(),
+        files: results.to_vec(),
+    };
+    save_cache(&cache)
+}
+
+// A failed save is reported alongside the walk errors, since the rebuilt entries are lost
+fn save_index_error(e: io::Error) -> IndexError {
+    IndexError {
+        path: cache_path().to_string_lossy().to_string(),
+        message: format!("Index not saved: {}", e),
+    }
+}
+
+// Folders scanned when the user hasn't configured any
+fn builtin_scan_folders() -> Vec<String> {
+    if cfg!(target_os = "linux") {
+        return get_xdg_scan_directories();
+    }
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    vec![
+        format!("{}/Documents", home),
+        format!("{}/Downloads", home),
+        format!("{}/Desktop", home),
+    ]
+}
+
+/// XDG user directories and the folder each falls back to when the variable is unset
+const XDG_SCAN_DIRECTORIES: &[(&str, &str)] = &[
+    ("XDG_DOCUMENTS_DIR", "Documents"),
+    ("XDG_DOWNLOAD_DIR", "Downloads"),
+    ("XDG_MUSIC_DIR", "Music"),
+    ("XDG_PICTURES_DIR", "Pictures"),
+    ("XDG_VIDEOS_DIR", "Videos"),
+];
+
+/// The Linux XDG user directories, read from the environment with `~/Documents`-style fallbacks
+#[uniffi::export]
+pub fn get_xdg_scan_directories() -> Vec<String> {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    XDG_SCAN_DIRECTORIES
+        .iter()
+        .map(|(var, fallback)| match env::var(var) {
+            // user-dirs.dirs writes values as "$HOME/..."
+            Ok(value) if !value.trim().is_empty() => value.trim().replacen("$HOME", &home, 1),
+            _ => format!("{}/{}", home, fallback),
+        })
+        .collect()
+}
+
+/// Folders `rebuild_index` scans: the configured list, or the built-in defaults
+#[uniffi::export]
+pub fn get_default_scan_folders() -> Vec<String> {
+    let configured = load_config().default_scan_folders;
+    if configured.is_empty() {
+        builtin_scan_folders()
+    } else {
+        configured
+    }
+}
+
+/// Persist the folders `rebuild_index` scans. An empty list restores the built-in defaults.
+#[uniffi::export]
+pub fn set_default_scan_folders(paths: Vec<String>) -> FileOpResult {
+    let missing: Vec<&String> = paths.iter().filter(|p| !Path::new(p).is_dir()).collect();
+    if !missing.is_empty() {
+        return FileOpResult {
+            success: false,
+            message: format!("Not a folder: {}", missing.iter().map(|p| p.as_str()).collect::<Vec<_>>().join(", ")),
+            affected_count: 0,
+        };
+    }
+    
+    let mut config = load_config();
+    let count = paths.len() as i32;
+    config.default_scan_folders = paths;
+    
+    match save_config(&config) {
+        Ok(_) => FileOpResult {
+            success: true,
+            message: format!("Saved {} scan folders", count),
+            affected_count: count,
+        },
+        Err(e) => FileOpResult {
+            success: false,
+            message: format!("Failed to save config: {}", e),
+            affected_count: 0,
+        },
+    }
+}
+
+// Build artifacts generated in bulk that only bloat the index.
+// `*` matches any run of characters; patterns are compared against entry names.
+const NOISE_PATTERNS: &[&str] = &["__pycache__", "*.pyc", "*.class", "*.o", "*.d"];
+
+// The active noise patterns: the defaults above plus user adjustments
+static ACTIVE_NOISE_PATTERNS: LazyLock<RwLock<Vec<String>>> =
+    LazyLock::new(|| RwLock::new(NOISE_PATTERNS.iter().map(|p| p.to_string()).collect()));
+
+/// Skip entries whose name matches `pattern` (e.g. "*.tmp", "node_modules") on rebuild
+#[uniffi::export]
+pub fn add_noise_pattern(pattern: String) {
+    let pattern = pattern.trim().to_string();
+    if pattern.is_empty() {
+        return;
+    }
+    if let Ok(mut patterns) = ACTIVE_NOISE_PATTERNS.write() {
+        if !patterns.contains(&pattern) {
+            patterns.push(pattern);
+        }
+    }
+}
+
+/// Stop skipping entries matching `pattern`, including the built-in ones
+#[uniffi::export]
+pub fn remove_noise_pattern(pattern: String) {
+    if let Ok(mut patterns) = ACTIVE_NOISE_PATTERNS.write() {
+        patterns.retain(|p| p != pattern.trim());
+    }
+}
+
+fn noise_patterns() -> Vec<String> {
+    ACTIVE_NOISE_PATTERNS.read().map(|p| p.clone()).unwrap_or_default()
+}
+
+// Glob-style match where `*` stands for any (possibly empty) run of characters
+fn matches_noise_pattern(name: &str, pattern: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    
+    let remaining: Vec<&str> = parts.collect();
+    let Some((last, middle)) = remaining.split_last() else {
+        // No wildcard: exact match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+// BLAKE3 hex digest of a file's contents
+fn blake3_file(path: &Path) -> Option<String> {
+    let mut file = fs::File::open(path).ok()?;
+    let mut hasher = blake3::Hasher::new();
+    io::copy(&mut file, &mut hasher).ok()?;
+    Some(hasher.finalize().to_hex().to_string())
+}
+
+// Number of pages in a PDF, or None if it can't be parsed (e.g. encrypted or damaged)
+fn pdf_page_count(path: &Path) -> Option<u32> {
+    let document = lopdf::Document::load(path).ok()?;
+    Some(document.get_pages().len() as u32)
+}
+
+// System directories that are never indexed, even when a scan root contains them
+const ALWAYS_EXCLUDE: &[&str] = &["/Library", "/System", "/private", "/usr", "/bin", "/sbin"];
+
+// ALWAYS_EXCLUDE plus directories added with `add_system_exclusion`
+static SYSTEM_EXCLUSIONS: LazyLock<RwLock<Vec<String>>> =
+    LazyLock::new(|| RwLock::new(ALWAYS_EXCLUDE.iter().map(|p| p.to_string()).collect()));
+
+/// Never index anything at or below `path`
+#[uniffi::export]
+pub fn add_system_exclusion(path: String) {
+    let path = path.trim().trim_end_matches('/').to_string();
+    if path.is_empty() {
+        return;
+    }
+    if let Ok(mut exclusions) = SYSTEM_EXCLUSIONS.write() {
+        if !exclusions.contains(&path) {
+            exclusions.push(path);
+        }
+    }
+}
+
+/// Directories that are never indexed, built-in ones first
+#[uniffi::export]
+pub fn list_system_exclusions() -> Vec<String> {
+    SYSTEM_EXCLUSIONS.read().map(|e| e.clone()).unwrap_or_default()
+}
+
+// Extra per-entry check applied by `walk_roots`: (path, is_folder) -> keep
+type EntryFilter = Arc<dyn Fn(&Path, bool) -> bool + Send + Sync>;
+
+// Shared between walker threads while rebuilding
+#[derive(Default)]
+struct WalkCounters {
+    scanned: AtomicU32,
+    indexed: AtomicU32,
+    errors: Mutex<Vec<IndexError>>,
+}
+
+impl WalkCounters {
+    fn record_error(&self, path: String, message: String) {
+        if let Ok(mut errors) = self.errors.lock() {
+            errors.push(IndexError { path, message });
+        }
+    }
+}
+
+// The path a walk error refers to, if it carries one
+fn walk_error_path(error: &ignore::Error) -> String {
+    match error {
+        ignore::Error::WithPath { path, .. } => path.to_string_lossy().to_string(),
+        ignore::Error::Loop { child, .. } => child.to_string_lossy().to_string(),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => walk_error_path(err),
+        _ => String::new(),
+    }
+}
+
+// Walker for one scan root with the rebuild's depth, ignore, hidden-dir,
+// noise-pattern and exclusion rules applied
+fn index_walk_builder(root: &Path, config: &WalkConfig) -> WalkBuilder {
+    let skip_hidden_dirs = config.skip_hidden_dirs;
+    let noise = noise_patterns();
+    let mut exclusions = list_system_exclusions();
+    exclusions.extend(config.exclude_paths.iter().cloned());
+    
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .hidden(true)
+        .git_ignore(true)
+        .max_depth(Some(config.max_depth as usize))
+        .follow_links(config.follow_symlinks)
+        .filter_entry(move |e| {
+            if exclusions.iter().any(|excluded| e.path().starts_with(excluded)) {
+                return false;
+            }
+            if e.depth() == 0 {
+                return true;
+            }
+            let name = e.file_name().to_string_lossy();
+            if skip_hidden_dirs && name.starts_with('.') {
+                return false;
+            }
+            !noise.iter().any(|pattern| matches_noise_pattern(&name, pattern))
+        });
+    builder
+}
+
+/// Files without an extension (shell scripts, Makefiles, ...) below `roots`,
+/// which the index skips. Uses the same depth and ignore rules as `rebuild_index`.
+/// Newest first.
+#[uniffi::export]
+pub fn find_files_without_extension(roots: Vec<String>) -> Vec<SearchResult> {
+    let config = WalkConfig::default();
+    let network_mounts = network_mount_points();
+    let mut results = Vec::new();
+    
+    for root in &roots {
+        for entry in index_walk_builder(Path::new(root), &config).build().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !entry.file_type().is_some_and(|ft| ft.is_file()) || path.extension().is_some() {
+                continue;
+            }
+            if let Ok(metadata) = entry.metadata() {
+                let mut result = build_result(path, &FileMetadata::from_std(&metadata));
+                result.is_network_path = is_network_path(path, &network_mounts);
+                results.push(result);
+            }
+        }
+    }
+    
+    sort_newest_first(&mut results);
+    results
+}
+
+// Index-time enrichment configured in `WalkConfig`
+fn enrich_result(result: &mut SearchResult, path: &Path, is_file: bool, compute_checksums: bool, enrich_pdfs: bool) {
+    if compute_checksums && is_file {
+        result.checksum = blake3_file(path).unwrap_or_default();
+    }
+    if enrich_pdfs && result.file_kind == "PDF Document" {
+        result.page_count = pdf_page_count(path);
+    }
+}
+
+// Add an entry to the walk's results and the checkpoint, if there is one
+fn push_indexed(
+    results: &Mutex<Vec<SearchResult>>,
+    counters: &WalkCounters,
+    checkpoint: &Option<CheckpointWriter>,
+    result: SearchResult,
+) {
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.append(&result);
+    }
+    if let Ok(mut lock) = results.lock() {
+        lock.push(result);
+        counters.indexed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// Walk each root in parallel and return its indexable entries (newest first) with stats.
+// With a `checkpoint` path, every entry is appended there as it's indexed. Entries left
+// in it by an interrupted walk with the same roots and config keep their checksum and
+// page count when the file's size and date are unchanged, instead of being re-read.
+fn walk_roots(
+    scan_folders: &[String],
+    config: &WalkConfig,
+    entry_filter: Option<EntryFilter>,
+    checkpoint: Option<&Path>,
+) -> (Vec<SearchResult>, RebuildStats) {
+    walk_roots_in(&RealFileSystem, scan_folders, config, entry_filter, checkpoint)
+}
+
+// `walk_roots` over any `TestableFileSystem`
+fn walk_roots_in<FS: TestableFileSystem>(
+    file_system: &FS,
+    scan_folders: &[String],
+    config: &WalkConfig,
+    entry_filter: Option<EntryFilter>,
+    checkpoint: Option<&Path>,
+) -> (Vec<SearchResult>, RebuildStats) {
+    let started = Instant::now();
+    let allowed_ext = indexed_extensions();
+    let network_mounts = network_mount_points();
+    let counters = WalkCounters::default();
+    let key = checkpoint_key(scan_folders, config);
+    let resumed = checkpoint.map(|path| load_checkpoint(path, &key)).unwrap_or_default();
+    // Created after loading, since it truncates the old checkpoint
+    let checkpoint = checkpoint.and_then(|path| CheckpointWriter::create(path, &key).ok());
+    
+    let results = Mutex::new(Vec::new());
+    
+    for folder in scan_folders {
+        let folder_path = Path::new(folder);
+        if file_system.metadata(folder_path).is_err() {
+            continue;
+        }
+        if config.skip_network_volumes && is_network_path(folder_path, &network_mounts) {
+            continue;
+        }
+        
+        file_system.walk(folder_path, config, &|entry_result| {
+            let entry = match entry_result {
+                Ok(entry) => entry,
+                Err(e) => {
+                    counters.record_error(e.path, e.message);
+                    return true;
+                }
+            };
+            counters.scanned.fetch_add(1, Ordering::Relaxed);
+            
+            let path = entry.path.as_path();
+            let is_network = is_network_path(path, &network_mounts);
+            
+            // Network mounts can be nested inside local folders
+            if config.skip_network_volumes && is_network {
+                return false;
+            }
+            
+            // Filter by extension (files without one are skipped)
+            if !is_indexable(path, entry.is_file, &allowed_ext) {
+                return true;
+            }
+            if let Some(filter) = &entry_filter {
+                if !filter(path, entry.is_dir) {
+                    return true;
+                }
+            }
+            
+            let mut result = match file_system.metadata(path) {
+                Ok(metadata) => build_result(path, &metadata),
+                Err(e) => {
+                    counters.record_error(path.to_string_lossy().to_string(), e.to_string());
+                    return true;
+                }
+            };
+            result.is_network_path = is_network;
+            result.is_alias = config.detect_aliases && entry.is_file && is_finder_alias(path);
+            match resumed.get(&result.file_path) {
+                Some(previous) if previous.date_value == result.date_value && previous.file_size == result.file_size => {
+                    result.checksum = previous.checksum.clone();
+                    result.page_count = previous.page_count;
+                }
+                _ => enrich_result(
+                    &mut result,
+                    path,
+                    entry.is_file,
+                    config.compute_checksums_during_index,
+                    config.enrich_pdf_metadata,
+                ),
+            }
+            
+            push_indexed(&results, &counters, &checkpoint, result);
+            true
+        });
+    }
+    
+    let mut final_results = results.into_inner().unwrap_or_default();
+    sort_newest_first(&mut final_results);
+    
+    let stats = RebuildStats {
+        files_scanned: counters.scanned.load(Ordering::Relaxed),
+        files_indexed: counters.indexed.load(Ordering::Relaxed),
+        errors: counters.errors.into_inner().unwrap_or_default(),
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    (final_results, stats)
+}
+
+/// Options controlling how `search_files_with_options` matches file names
+#[derive(uniffi::Record, Clone)]
+pub struct SearchOptions {
+    /// Normalize query and file names to NFC so composed and decomposed forms match
+    #[uniffi(default = true)]
+    pub unicode_normalization: bool,
+    /// Added to the score when the match starts at a word boundary (0 disables)
+    #[uniffi(default = 30)]
+    pub word_boundary_bonus: i64,
+    /// Keep at most this many results sharing the same file name (e.g. `README.md`)
+    #[uniffi(default = None)]
+    pub max_results_per_file_name: Option<u32>,
+    /// Added when the query appears, case-sensitively, in the name's acronym
+    /// ("HRH" for `HttpRequestHandler.swift`); 0 disables
+    #[uniffi(default = 50)]
+    pub acronym_bonus: i64,
+    /// Also match each query word against the name's camelCase / snake_case tokens,
+    /// so "request handler" finds `HttpRequestHandler.rs`
+    #[uniffi(default = true)]
+    pub use_token_matching: bool,
+    /// Divide scores by log2(name length + 1), rescaled so 16-character names are unchanged.
+    /// Long names score lower and short names higher, favouring tight matches on short
+    /// names over the same characters scattered through a long one.
+    #[uniffi(default = false)]
+    pub normalise_by_name_length: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            unicode_normalization: true,
+            word_boundary_bonus: 30,
+            max_results_per_file_name: None,
+            acronym_bonus: 50,
+            use_token_matching: true,
+            normalise_by_name_length: false,
+        }
+    }
+}
+
+// Keep only the top `limit` scoring results for each file name
+fn limit_per_file_name(mut results: Vec<SearchResult>, limit: u32) -> Vec<SearchResult> {
+    results.sort_by_key(|r| Reverse(r.score));
+    
+    let mut seen: HashMap<String, u32> = HashMap::new();
+    results.retain(|r| {
+        let count = seen.entry(r.file_name.clone()).or_insert(0);
+        *count += 1;
+        *count <= limit
+    });
+    results
+}
+
+// macOS stores names in NFD while typed queries are usually NFC
+fn normalize_nfc(s: &str) -> String {
+    s.nfc().collect()
+}
+
+// Start of a name, after a separator, a lower-to-upper transition, or a digit after a letter
+fn is_word_boundary(chars: &[char], index: usize) -> bool {
+    if index == 0 {
+        return true;
+    }
+    let (Some(&prev), Some(&current)) = (chars.get(index - 1), chars.get(index)) else {
+        return false;
+    };
+    
+    matches!(prev, '_' | '-' | '.' | ' ')
+        || (current.is_uppercase() && prev.is_lowercase())
+        || (current.is_ascii_digit() && prev.is_alphabetic())
+}
+
+// Fuzzy score for a file name under the given options, or None if it doesn't match.
+// `query` must already be normalized when `unicode_normalization` is set.
+fn score_file_name(
+    matcher: &SkimMatcherV2,
+    file_name: &str,
+    query: &str,
+    options: &SearchOptions,
+    acronyms: Option<&HashMap<String, String>>,
+) -> Option<i64> {
+    let normalized;
+    let name = if options.unicode_normalization {
+        normalized = normalize_nfc(file_name);
+        normalized.as_str()
+    } else {
+        file_name
+    };
+    
+    let fuzzy_score = if options.word_boundary_bonus == 0 {
+        matcher.fuzzy_match(name, query)
+    } else {
+        // Skim scores names as flat character runs; reward matches that start a word
+        matcher.fuzzy_indices(name, query).map(|(score, indices)| {
+            let chars: Vec<char> = name.chars().collect();
+            match indices.first() {
+                Some(&start) if is_word_boundary(&chars, start) => score + options.word_boundary_bonus,
+                _ => score,
+            }
+        })
+    };
+    let fuzzy_score = if options.use_token_matching {
+        fuzzy_score.max(token_match_score(matcher, name, query))
+    } else {
+        fuzzy_score
+    };
+    
+    let acronym_match = options.acronym_bonus != 0
+        && query.chars().count() > 1
+        && match acronyms.and_then(|acronyms| acronyms.get(file_name)) {
+            Some(cached) => cached.contains(query),
+            None => acronym(name).contains(query),
+        };
+    let score = match (fuzzy_score, acronym_match) {
+        (Some(score), true) => Some(score + options.acronym_bonus),
+        (None, true) => Some(options.acronym_bonus),
+        (score, false) => score,
+    };
+    
+    if options.normalise_by_name_length {
+        score.map(|score| normalise_by_name_length(score, name))
+    } else {
+        score
+    }
+}
+
+// Names this long keep their score unchanged under length normalisation
+const REFERENCE_NAME_LENGTH: usize = 16;
+
+// score / log2(len + 1), rescaled so a REFERENCE_NAME_LENGTH name is unaffected
+fn normalise_by_name_length(score: i64, name: &str) -> i64 {
+    let length_factor = |len: usize| ((len.max(1) + 1) as f64).log2();
+    let scale = length_factor(REFERENCE_NAME_LENGTH) / length_factor(name.chars().count());
+    (score as f64 * scale).round() as i64
+}
+
+// Every whitespace-separated query word must fuzzy-match one of the name's tokens;
+// the score is the sum of each word's best token score
+fn token_match_score(matcher: &SkimMatcherV2, name: &str, query: &str) -> Option<i64> {
+    let tokens = split_words(name);
+    query
+        .split_whitespace()
+        .map(|word| tokens.iter().filter_map(|token| matcher.fuzzy_match(token, word)).max())
+        .sum()
+}
+
+// Upper-cased first letter of each word of the name's stem: "HttpRequestHandler.swift" -> "HRH"
+fn acronym(file_name: &str) -> String {
+    let stem = Path::new(file_name).file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    split_words(&stem)
+        .iter()
+        .filter_map(|word| word.chars().next())
+        .flat_map(|c| c.to_uppercase())
+        .collect()
+}
+
+#[uniffi::export]
+pub fn search_files(query: String) -> Vec<SearchResult> {
+    search_files_with_options(query, SearchOptions::default())
+}
+
+#[uniffi::export]
+pub fn search_files_with_options(query: String, options: SearchOptions) -> Vec<SearchResult> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    
+    let query = if options.unicode_normalization { normalize_nfc(&query) } else { query };
+    let max_per_file_name = options.max_results_per_file_name;
+    let threads = search_thread_count(&query);
+    let matcher = SkimMatcherV2::default().smart_case();
+    let open_counts = load_open_counts();
+    let counters = Arc::new(SearchCounters::default());
+    let walk_counters = counters.clone();
+    
+    // Without NFC normalization the raw name is matched, which the filter doesn't model
+    let query_chars = filter_chars(&query);
+    let char_filter = NAME_CHAR_FILTER
+        .lock()
+        .ok()
+        .and_then(|lock| lock.clone())
+        .filter(|_| options.unicode_normalization && !query_chars.is_empty());
+    let acronyms = cached_acronyms(&options);
+
+    let walk_started = Instant::now();
+    let mut final_results = walk_home_matching(threads, move |file_name, path| {
+        let match_started = Instant::now();
+        let skip = char_filter.as_ref().is_some_and(|filter| !may_match_chars(filter, file_name, &query_chars));
+        let score = if skip { None } else { score_file_name(&matcher, file_name, &query, &options, acronyms.as_deref()) }
+            .map(|score| score + open_counts.get(path).map_or(0, |&count| open_count_boost(count)));
+        
+        walk_counters.visited.fetch_add(1, Ordering::Relaxed);
+        walk_counters.match_nanos.fetch_add(match_started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        if score.is_some() {
+            walk_counters.matched.fetch_add(1, Ordering::Relaxed);
+        }
+        score
+    });
+    let walk_duration = walk_started.elapsed();
+
+    let sort_started = Instant::now();
+    if let Some(limit) = max_per_file_name {
+        final_results = limit_per_file_name(final_results, limit);
+    }
+    sort_by_score(&mut final_results);
+    final_results.truncate(50);
+
+    if let Ok(mut stats) = LAST_SEARCH_STATS.lock() {
+        *stats = SearchPerfStats {
+            walk_duration_ms: walk_duration.as_millis() as u64,
+            match_duration_ms: counters.match_nanos.load(Ordering::Relaxed) / 1_000_000,
+            sort_duration_ms: sort_started.elapsed().as_millis() as u64,
+            total_files_visited: counters.visited.load(Ordering::Relaxed),
+            total_files_matched: counters.matched.load(Ordering::Relaxed),
+        };
+    }
+
+    final_results
+}
+
+/// Timing breakdown of the most recent `search_files` call
+#[derive(uniffi::Record, Clone, Default)]
+pub struct SearchPerfStats {
+    pub walk_duration_ms: u64,    // Wall time of the parallel walk, matching included
+    pub match_duration_ms: u64,   // Time spent scoring names, summed across threads
+    pub sort_duration_ms: u64,
+    pub total_files_visited: u32,
+    pub total_files_matched: u32,
+}
+
+static LAST_SEARCH_STATS: Mutex<SearchPerfStats> = Mutex::new(SearchPerfStats {
+    walk_duration_ms: 0,
+    match_duration_ms: 0,
+    sort_duration_ms: 0,
+    total_files_visited: 0,
+    total_files_matched: 0,
+});
+
+// Shared between walker threads while searching
+#[derive(Default)]
+struct SearchCounters {
+    visited: AtomicU32,
+    matched: AtomicU32,
+    match_nanos: AtomicU64,
+}
+
+/// Stats recorded by the last `search_files` / `search_files_with_options` call
+#[uniffi::export]
+pub fn get_search_performance_stats() -> SearchPerfStats {
+    LAST_SEARCH_STATS.lock().map(|s| s.clone()).unwrap_or_default()
+}
+
+/// Search for entries matching every whitespace-separated token of the query,
+/// in any order. Each token may match the file name or its full path; the
+/// result's score is the sum of the token scores.
+#[uniffi::export]
+pub fn search_files_all_tokens(query: String) -> Vec<SearchResult> {
+    let tokens: Vec<String> = query.split_whitespace().map(normalize_nfc).collect();
+    if tokens.is_empty() {
+        return Vec::new();
+    }
+    
+    let threads = search_thread_count(&query);
+    let matcher = SkimMatcherV2::default().smart_case();
+    let mut results = walk_home_matching(threads, move |file_name, path| {
+        let name = normalize_nfc(file_name);
+        let path = normalize_nfc(path);
+        tokens
+            .iter()
+            .map(|token| {
+                matcher.fuzzy_match(&name, token).or_else(|| matcher.fuzzy_match(&path, token))
+            })
+            .sum::<Option<i64>>()
+    });
+    
+    sort_by_score(&mut results);
+    results.truncate(50);
+    results
+}
+
+// Abbreviations users commonly type, paired with the word they stand for
+const SEARCH_SYNONYMS: &[(&str, &str)] = &[
+    ("img", "image"),
+    ("doc", "document"),
+    ("vid", "video"),
+    ("cfg", "config"),
+];
+
+// The query plus every variant with one token swapped for its synonym (in either direction)
+fn expand_synonyms(query: &str) -> Vec<String> {
+    let tokens: Vec<&str> = query.split_whitespace().collect();
+    let mut variants = vec![tokens.join(" ")];
+    
+    for (i, token) in tokens.iter().enumerate() {
+        let lower = token.to_lowercase();
+        for &(short, long) in SEARCH_SYNONYMS {
+            let replacement = if lower == short {
+                long
+            } else if lower == long {
+                short
+            } else {
+                continue;
+            };
+            let mut swapped = tokens.clone();
+            swapped[i] = replacement;
+            let variant = swapped.join(" ");
+            if !variants.contains(&variant) {
+                variants.push(variant);
+            }
+        }
+    }
+    variants
+}
+
+/// Search for the query and its common abbreviation/synonym expansions
+/// (`img` ↔ `image`, `cfg` ↔ `config`, ...). Each entry appears once,
+/// scored by the best-matching variant.
+#[uniffi::export]
+pub fn search_files_with_synonyms(query: String) -> Vec<SearchResult> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    
+    let variants: Vec<String> = expand_synonyms(&normalize_nfc(&query));
+    let threads = search_thread_count(&query);
+    let matcher = SkimMatcherV2::default().smart_case();
+    let options = SearchOptions::default();
+    let acronyms = cached_acronyms(&options);
+    
+    let mut results = walk_home_matching(threads, move |file_name, _path| {
+        variants
+            .iter()
+            .filter_map(|variant| score_file_name(&matcher, file_name, variant, &options, acronyms.as_deref()))
+            .max()
+    });
+    
+    sort_by_score(&mut results);
+    results.truncate(50);
+    results
+}
+
+// Score for a numeric token found exactly in a name; near misses within range get half
+const NUMERIC_EXACT_SCORE: i64 = 50;
+
+/// Search where numeric tokens match any number in the file name within ±5%
+/// of their value ("2023" also finds "2022-report" and "v2024"), while the other
+/// tokens are fuzzy-matched as usual. Every numeric token must match.
+#[uniffi::export]
+pub fn search_files_with_numeric_wildcard(query: String) -> Vec<SearchResult> {
+    let (numbers, words): (Vec<&str>, Vec<&str>) =
+        query.split_whitespace().partition(|token| token.parse::<u64>().is_ok());
+    let numbers: Vec<u64> = numbers.iter().filter_map(|token| token.parse().ok()).collect();
+    if numbers.is_empty() {
+        return search_files(query);
+    }
+    
+    let text = normalize_nfc(&words.join(" "));
+    let threads = search_thread_count(&query);
+    let matcher = SkimMatcherV2::default().smart_case();
+    let options = SearchOptions::default();
+    let acronyms = cached_acronyms(&options);
+    
+    let mut results = walk_home_matching(threads, move |file_name, _path| {
+        let text_score = if text.is_empty() {
+            0
+        } else {
+            score_file_name(&matcher, file_name, &text, &options, acronyms.as_deref())?
+        };
+        
+        let found = numbers_in(file_name);
+        let mut numeric_score = 0;
+        for &wanted in &numbers {
+            let tolerance = wanted / 20;
+            let best = found
+                .iter()
+                .filter(|&&n| n.abs_diff(wanted) <= tolerance)
+                .map(|&n| if n == wanted { NUMERIC_EXACT_SCORE } else { NUMERIC_EXACT_SCORE / 2 })
+                .max()?;
+            numeric_score += best;
+        }
+        Some(text_score + numeric_score)
+    });
+    
+    sort_by_score(&mut results);
+    results.truncate(50);
+    results
+}
+
+// Every run of ASCII digits in a name, parsed (runs too long for u64 are skipped)
+fn numbers_in(name: &str) -> Vec<u64> {
+    name.split(|c: char| !c.is_ascii_digit())
+        .filter(|run| !run.is_empty())
+        .filter_map(|run| run.parse().ok())
+        .collect()
+}
+
+/// Search with English (Porter) stemming applied to the query and to the words
+/// of each file name, so "report" also finds "reports", "reporting" and "reported".
+/// Falls back to `search_files` when nothing matches after stemming.
+#[uniffi::export]
+pub fn search_files_stemmed(query: String) -> Vec<SearchResult> {
+    use rust_stemmers::{Algorithm, Stemmer};
+    
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    
+    let stemmer = Stemmer::create(Algorithm::English);
+    let stem_words = move |text: &str| -> String {
+        split_words(text)
+            .iter()
+            .map(|word| stemmer.stem(&word.to_lowercase()).into_owned())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    
+    let stemmed_query = stem_words(&normalize_nfc(&query));
+    let threads = search_thread_count(&query);
+    let matcher = SkimMatcherV2::default();
+    
+    let mut results = walk_home_matching(threads, move |file_name, _path| {
+        let name = normalize_nfc(file_name);
+        let stem = Path::new(&name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        matcher.fuzzy_match(&stem_words(&stem), &stemmed_query)
+    });
+    
+    if results.is_empty() {
+        return search_files(query);
+    }
+    sort_by_score(&mut results);
+    results.truncate(50);
+    results
+}
+
+// Fuzzy matches are shifted above this so phonetic-only matches always rank below them
+const PHONETIC_TIER_OFFSET: i64 = 1000;
+
+/// Search by how words sound (Double Metaphone), for names the user can't spell.
+/// Regular fuzzy matches rank first; entries whose words only sound like
+/// a query token follow, scored by how many tokens matched.
+#[uniffi::export]
+pub fn search_files_phonetic(query: String) -> Vec<SearchResult> {
+    use rphonetic::{DoubleMetaphone, Encoder};
+    
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    
+    let encoder = DoubleMetaphone::default();
+    let query_codes: Vec<String> = query
+        .split_whitespace()
+        .map(|token| encoder.encode(token))
+        .filter(|code| !code.is_empty())
+        .collect();
+    let query = normalize_nfc(&query);
+    let threads = search_thread_count(&query);
+    let matcher = SkimMatcherV2::default().smart_case();
+    
+    let mut results = walk_home_matching(threads, move |file_name, _path| {
+        if let Some(score) = matcher.fuzzy_match(&normalize_nfc(file_name), &query) {
+            return Some(score + PHONETIC_TIER_OFFSET);
+        }
+        
+        let stem = Path::new(file_name).file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+        let name_codes: HashSet<String> = split_words(&stem)
+            .iter()
+            .map(|word| encoder.encode(word))
+            .filter(|code| !code.is_empty())
+            .collect();
+        
+        let matched = query_codes.iter().filter(|code| name_codes.contains(*code)).count() as i64;
+        (matched > 0).then_some(matched.min(PHONETIC_TIER_OFFSET))
+    });
+    
+    sort_by_score(&mut results);
+    results.truncate(50);
+    results
+}
+
+// Split a name into words on separators and lower-to-upper case transitions
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev: Option<char> = None;
+    
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else {
+            if let Some(p) = prev {
+                if c.is_uppercase() && p.is_lowercase() && !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+            }
+            current.push(c);
+        }
+        prev = Some(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+// Search order: highest score first, ties broken by the configured field
+fn sort_by_score(results: &mut [SearchResult]) {
+    let tiebreaker = tiebreaker_field();
+    results.sort_by(|a, b| {
+        b.score.cmp(&a.score).then_with(|| compare_by_field(a, b, tiebreaker))
+    });
+}
+
+// Short queries match a large share of files and benefit from parallelism;
+// long, selective ones don't, so scale down threads to save battery
+fn search_thread_count(query: &str) -> usize {
+    4usize.saturating_sub(query.chars().count() / 4).clamp(1, 4)
+}
+
+// Walk $HOME in parallel, keeping entries for which `score_entry(file_name, path)`
+// returns a score. Pinned paths get a bonus; collection stops after 2000 matches.
+fn walk_home_matching<F>(threads: usize, score_entry: F) -> Vec<SearchResult>
+where
+    F: Fn(&str, &str) -> Option<i64> + Send + Sync + 'static,
+{
+    let root_path = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let score_entry = Arc::new(score_entry);
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let network_mounts = Arc::new(network_mount_points());
+    let pinned: Arc<HashSet<String>> = Arc::new(load_config().pinned_paths.into_iter().collect());
+    let active_directory = Arc::new(ACTIVE_DIRECTORY.lock().ok().and_then(|d| d.clone()));
+
+    let walker = WalkBuilder::new(root_path)
+        .hidden(true)
+        .git_ignore(true)
+        .max_depth(Some(6))
+        .threads(threads)
+        .build_parallel();
+
+    let results_clone = results.clone();
+
+    walker.run(move || {
+        let results = results_clone.clone();
+        let score_entry = score_entry.clone();
+        let network_mounts = network_mounts.clone();
+        let pinned = pinned.clone();
+        let active_directory = active_directory.clone();
+        
+        Box::new(move |entry_result| {
+            if let Ok(entry) = entry_result {
+                let file_name = entry.file_name().to_string_lossy();
+                let path = entry.path();
+                let path_str = path.to_string_lossy().to_string();
+                
+                if let Some(score) = score_entry(&file_name, &path_str) {
+                    let is_folder = entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false);
+                    
+                    let (size, date_value, date_kind) = if let Ok(metadata) = entry.metadata() {
+                        let (dv, dk) = get_best_date(&FileMetadata::from_std(&metadata));
+                        (metadata.len(), dv, dk)
+                    } else {
+                        (0, 0, "Unknown")
+                    };
+                    
+                    let file_kind = get_file_kind(path, is_folder);
+                    let score = match active_directory.as_deref() {
+                        Some(dir) if path.starts_with(dir) => score * ACTIVE_DIRECTORY_MULTIPLIER,
+                        _ => score,
+                    };
+                    let score = if pinned.contains(&path_str) {
+                        score + PINNED_SCORE_BONUS
+                    } else {
+                        score
+                    };
+
+                    if let Ok(mut lock) = results.lock() {
+                        if lock.len() < 2000 {
+                            lock.push(SearchResult {
+                                file_name: file_name.to_string(),
+                                file_path: path_str,
+                                file_size: size,
+                                is_folder,
+                                score,
+                                date_value,
+                                date_kind: date_kind.to_string(),
+                                file_kind,
+                                pretty_date: format_relative_date(date_value),
+                                is_network_path: is_network_path(path, &network_mounts),
+                                is_alias: false,
+                                checksum: String::new(),
+                                page_count: None,
+                            });
+                        } else {
+                            return ignore::WalkState::Quit;
+                        }
+                    }
+                }
+            }
+            ignore::WalkState::Continue
+        })
+    });
+
+    results.lock().map(|matched| matched.clone()).unwrap_or_default()
+}
+
+// Added to pinned results so they outrank any fuzzy match score
+const PINNED_SCORE_BONUS: i64 = 1_000_000_000;
+
+/// Pin paths so they always appear at the top of `search_files` results
+#[uniffi::export]
+pub fn pin_paths(paths: Vec<String>) -> FileOpResult {
+    let mut config = load_config();
+    let mut added = 0;
+    
+    for path in paths {
+        if !config.pinned_paths.contains(&path) {
+            config.pinned_paths.push(path);
+            added += 1;
+        }
+    }
+    
+    match save_config(&config) {
+        Ok(_) => FileOpResult {
+            success: true,
+            message: format!("Pinned {} paths", added),
+            affected_count: added,
+        },
+        Err(e) => FileOpResult {
+            success: false,
+            message: format!("Failed to save config: {}", e),
+            affected_count: 0,
+        },
+    }
+}
+
+/// Remove a path from the pinned list
+#[uniffi::export]
+pub fn unpin_path(path: String) -> FileOpResult {
+    let mut config = load_config();
+    let before = config.pinned_paths.len();
+    config.pinned_paths.retain(|p| p != &path);
+    
+    if config.pinned_paths.len() == before {
+        return FileOpResult {
+            success: false,
+            message: format!("'{}' is not pinned", path),
+            affected_count: 0,
+        };
+    }
+    
+    match save_config(&config) {
+        Ok(_) => FileOpResult {
+            success: true,
+            message: "Unpinned path".to_string(),
+            affected_count: 1,
+        },
+        Err(e) => FileOpResult {
+            success: false,
+            message: format!("Failed to save config: {}", e),
+            affected_count: 0,
+        },
+    }
+}
+
+/// List pinned paths in the order they were pinned
+#[uniffi::export]
+pub fn list_pinned_paths() -> Vec<String> {
+    load_config().pinned_paths
+}
+
+// Directory the user is currently looking at (e.g. the frontmost Finder window)
+static ACTIVE_DIRECTORY: Mutex<Option<String>> = Mutex::new(None);
+
+// Score multiplier for results inside the active directory
+const ACTIVE_DIRECTORY_MULTIPLIER: i64 = 2;
+
+/// Rank results inside `path` higher in subsequent searches. An empty path clears it.
+#[uniffi::export]
+pub fn set_active_directory(path: String) {
+    if let Ok(mut lock) = ACTIVE_DIRECTORY.lock() {
+        *lock = if path.is_empty() { None } else { Some(path) };
+    }
+}
+
+// Weight of the open-history boost: score += log2(open_count + 1) * weight
+const OPEN_COUNT_BOOST: f64 = 20.0;
+
+fn opens_path() -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(format!("{}/.fast-finder-opens.json", home))
+}
+
+// Path -> number of times the user opened it
+fn load_open_counts() -> HashMap<String, u32> {
+    read_private_json(&opens_path()).unwrap_or_default()
+}
+
+/// Note that the user opened `path`, so it ranks higher in later searches
+#[uniffi::export]
+pub fn record_file_open(path: String) {
+    // Rewriting an encrypted history we can't read would lose it
+    if is_locked(&opens_path()) {
+        return;
+    }
+    let mut counts = load_open_counts();
+    *counts.entry(path).or_insert(0) += 1;
+    let _ = write_private_json(&opens_path(), &counts);
+}
+
+fn open_count_boost(open_count: u32) -> i64 {
+    ((open_count as f64 + 1.0).log2() * OPEN_COUNT_BOOST) as i64
+}
+
+/// The `n` most frequently opened files that still exist, most opened first.
+/// Each result's score is its open count.
+#[uniffi::export]
+pub fn get_most_opened_files(n: u32) -> Vec<SearchResult> {
+    let mut counts: Vec<(String, u32)> = load_open_counts().into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    
+    counts
+        .into_iter()
+        .filter_map(|(path, count)| {
+            let mut result = stat_result(Path::new(&path))?;
+            result.score = count as i64;
+            Some(result)
+        })
+        .take(n as usize)
+        .collect()
+}
+
+/// Refresh a single path in the cache after a move, rename or create,
+/// without rebuilding the whole index
+#[uniffi::export]
+pub fn reindex_path(path: String) -> FileOpResult {
+    let target = Path::new(&path);
+    let result = match stat_result(target) {
+        Some(r) => r,
+        None => return FileOpResult {
+            success: false,
+            message: format!("Cannot read '{}'", path),
+            affected_count: 0,
+        },
+    };
+    
+    if !is_indexable(target, !result.is_folder, &indexed_extensions()) {
+        return FileOpResult {
+            success: false,
+            message: format!("'{}' is not an indexed file type", result.file_name),
+            affected_count: 0,
+        };
+    }
+    // A locked cache loads as empty, so saving it would drop every other entry
+    if is_locked(&cache_path()) {
+        return cache_locked_result();
+    }
+    
+    let mut cache = load_cache();
+    match cache.files.iter_mut().find(|f| f.file_path == result.file_path) {
+        Some(existing) => *existing = result,
+        None => cache.files.push(result),
+    }
+    sort_newest_first(&mut cache.files);
+    if let Err(e) = save_cache(&cache) {
+        return FileOpResult {
+            success: false,
+            message: format!("Failed to save index: {}", e),
+            affected_count: 0,
+        };
+    }
+    
+    FileOpResult {
+        success: true,
+        message: "Updated index entry".to_string(),
+        affected_count: 1,
+    }
+}
+
+/// Drop a deleted path (and anything under it, for folders) from the cache
+#[uniffi::export]
+pub fn remove_path_from_cache(path: String) -> FileOpResult {
+    if is_locked(&cache_path()) {
+        return cache_locked_result();
+    }
+    
+    let mut cache = load_cache();
+    let before = cache.files.len();
+    cache.files.retain(|f| !Path::new(&f.file_path).starts_with(&path));
+    let removed = before - cache.files.len();
+    
+    if removed > 0 {
+        if let Err(e) = save_cache(&cache) {
+            return FileOpResult {
+                success: false,
+                message: format!("Failed to save index: {}", e),
+                affected_count: 0,
+            };
+        }
+    }
+    
+    FileOpResult {
+        success: true,
+        message: format!("Removed {} entries from index", removed),
+        affected_count: removed as i32,
+    }
+}
+
+fn cache_locked_result() -> FileOpResult {
+    FileOpResult {
+        success: false,
+        message: cache_locked_error().to_string(),
+        affected_count: 0,
+    }
+}
+
+/// Fuzzy search the cached index without touching the filesystem.
+/// Fast enough to run on every keystroke, but may miss files created
+/// since the last `rebuild_index`.
+#[uniffi::export]
+pub fn search_in_cache(query: String) -> Vec<SearchResult> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    
+    rank_by_query(&query, load_cache().files)
+}
+
+/// Search results the caller already holds (e.g. from `load_cached_index`)
+/// without touching the disk. Returns the top 50 file name matches.
+#[uniffi::export]
+pub fn search_in_results(query: String, results: Vec<SearchResult>) -> Vec<SearchResult> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    
+    rank_by_query(&query, results)
+}
+
+// Score each entry's file name against the query, keeping the top 50 matches
+fn rank_by_query(query: &str, files: Vec<SearchResult>) -> Vec<SearchResult> {
+    let matcher = SkimMatcherV2::default().smart_case();
+    let query = normalize_nfc(query);
+    
+    let mut matches: Vec<SearchResult> = files
+        .into_iter()
+        .filter_map(|mut file| {
+            let score = matcher.fuzzy_match(&normalize_nfc(&file.file_name), &query)?;
+            file.score = score;
+            Some(file)
+        })
+        .collect();
+    
+    matches.sort_by_key(|m| Reverse(m.score));
+    matches.truncate(50);
+    matches
+}
+
+/// Fuzzy-match the query against the file names of an existing list of paths
+/// (e.g. `git ls-files` output) instead of walking the disk. Only matching
+/// paths are stat'ed; ones that no longer exist are dropped.
+#[uniffi::export]
+pub fn search_in_paths(query: String, paths: Vec<String>) -> Vec<SearchResult> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    
+    let matcher = SkimMatcherV2::default().smart_case();
+    let query = normalize_nfc(&query);
+    let network_mounts = network_mount_points();
+    
+    let mut matches: Vec<SearchResult> = paths
+        .iter()
+        .filter_map(|path_str| {
+            let path = Path::new(path_str);
+            let file_name = path.file_name()?.to_string_lossy();
+            let score = matcher.fuzzy_match(&normalize_nfc(&file_name), &query)?;
+            
+            let metadata = fs::metadata(path).ok()?;
+            let mut result = build_result(path, &FileMetadata::from_std(&metadata));
+            result.is_network_path = is_network_path(path, &network_mounts);
+            result.score = score;
+            Some(result)
+        })
+        .collect();
+    
+    sort_by_score(&mut matches);
+    matches
+}
+
+/// One line of a file matching a content search
+#[derive(uniffi::Record, Clone)]
+pub struct ContentMatch {
+    pub file_path: String,
+    pub line_number: u32,     // 1-based
+    pub line_content: String, // Without the trailing newline
+    pub match_start: u32,     // Character offsets of the first match within the line
+    pub match_end: u32,
+}
+
+const MAX_CONTENT_MATCHES_PER_FILE: usize = 100;
+const MAX_CONTENT_MATCHES: usize = 1000;
+
+// Longer lines are minified JSON, logs or binary data; a file is abandoned at the first one
+const MAX_CONTENT_LINE_BYTES: u64 = 64 * 1024;
+
+/// Why a content search couldn't run
+#[derive(uniffi::Error, Debug, Clone)]
+pub enum ContentSearchError {
+    InvalidPattern(String),
+}
+
+impl std::fmt::Display for ContentSearchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentSearchError::InvalidPattern(message) => write!(f, "Invalid pattern: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ContentSearchError {}
+
+/// Files below `root` whose contents match the regular expression `pattern`, one entry per
+/// matching line. Only files with one of `extensions` (e.g. "rs" or ".md", case-insensitive)
+/// are read; an empty list reads every file. Reading a file stops at its first line that
+/// isn't valid UTF-8 or is longer than 64 KB, which skips binary and minified files.
+/// At most 100 matches per file and 1000 in total.
+#[uniffi::export]
+pub fn find_files_matching_regex_in_content(
+    pattern: String,
+    extensions: Vec<String>,
+    root: String,
+) -> Result<Vec<ContentMatch>, ContentSearchError> {
+    let regex = regex::Regex::new(&pattern).map_err(|e| ContentSearchError::InvalidPattern(e.to_string()))?;
+    let extensions: HashSet<String> = extensions
+        .iter()
+        .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect();
+    
+    let mut matches = Vec::new();
+    let walker = WalkBuilder::new(&root).hidden(true).git_ignore(true).build();
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        if !extensions.is_empty() {
+            let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+            if !extensions.contains(&ext) {
+                continue;
+            }
+        }
+        
+        let remaining = MAX_CONTENT_MATCHES - matches.len();
+        matches.extend(regex_matches_in_file(&regex, path, remaining.min(MAX_CONTENT_MATCHES_PER_FILE)));
+        if matches.len() >= MAX_CONTENT_MATCHES {
+            break;
+        }
+    }
+    Ok(matches)
+}
+
+// Up to `limit` matching lines of one file; stops at the first line that isn't valid UTF-8
+// or exceeds MAX_CONTENT_LINE_BYTES
+fn regex_matches_in_file(regex: &regex::Regex, path: &Path, limit: usize) -> Vec<ContentMatch> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    let file_path = path.to_string_lossy().to_string();
+    let mut reader = BufReader::new(file);
+    let mut line = String::new();
+    let mut matches = Vec::new();
+    
+    for line_number in 1u32.. {
+        line.clear();
+        match io::Read::take(&mut reader, MAX_CONTENT_LINE_BYTES).read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(read) if read as u64 == MAX_CONTENT_LINE_BYTES && !line.ends_with('\n') => break,
+            Ok(_) => {}
+        }
+        let content = line.trim_end_matches(['\n', '\r']);
+        if let Some(found) = regex.find(content) {
+            let match_start = content[..found.start()].chars().count() as u32;
+            matches.push(ContentMatch {
+                file_path: file_path.clone(),
+                line_number,
+                line_content: content.to_string(),
+                match_start,
+                match_end: match_start + found.as_str().chars().count() as u32,
+            });
+            if matches.len() >= limit {
+                break;
+            }
+        }
+    }
+    matches
+}
+
+#[uniffi::export]
+pub fn get_recent_files() -> Vec<SearchResult> {
+    recent_files(7)
+}
+
+// Cached files modified in the last `days` days, newest first (top 50)
+fn recent_files(days: u32) -> Vec<SearchResult> {
+    let mut recent = recent_cache_entries(days);
+    recent.truncate(50);
+    recent
+}
+
+// Every cached file modified in the last `days` days, newest first
+fn recent_cache_entries(days: u32) -> Vec<SearchResult> {
+    // First try to return cached data for instant response
+    let cache = load_cache();
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    
+    let cutoff = now - (60 * 60 * 24 * days as i64);
+    
+    let mut recent: Vec<SearchResult> = cache.files
+        .into_iter()
+        .filter(|f| f.date_value > cutoff)
+        .collect();
+    
+    recent.sort_by_key(|r| Reverse(r.date_value));
+    recent
+}
+
+/// A balanced recent-files list: up to `per_kind_limit` of the newest files of
+/// each kind (documents, images, ...) from the last `days` days, merged newest first
+#[uniffi::export]
+pub fn get_recent_files_stratified(days: u32, per_kind_limit: u32) -> Vec<SearchResult> {
+    let mut taken: HashMap<String, u32> = HashMap::new();
+    
+    // Entries are already newest first, so the first N of each kind are its most recent
+    recent_cache_entries(days)
+        .into_iter()
+        .filter(|file| {
+            let count = taken.entry(file.file_kind.clone()).or_insert(0);
+            *count += 1;
+            *count <= per_kind_limit
+        })
+        .collect()
+}
+
+/// Recently opened files according to Spotlight's `kMDItemLastUsedDate`,
+/// which tracks opens more accurately than mtime. Falls back to the
+/// cache-based `get_recent_files` on other platforms or if Spotlight fails.
+#[uniffi::export]
+pub fn get_spotlight_recent_files(n: u32) -> Vec<SearchResult> {
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(results) = spotlight_recent_files(n as usize) {
+            return results;
+        }
+    }
+    
+    let mut recent = get_recent_files();
+    recent.truncate(n as usize);
+    recent
+}
+
+#[cfg(target_os = "macos")]
+fn spotlight_recent_files(n: usize) -> Option<Vec<SearchResult>> {
+    use std::process::Command;
+    
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let output = Command::new("mdfind")
+        .args(["-0", "-onlyin", &home, "kMDItemLastUsedDate >= $time.today(-30)"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    
+    let paths: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_string())
+        .collect();
+    
+    // Look up the last-used dates in batches; `mdls -raw` separates values with NUL
+    let mut dated: Vec<(String, i64)> = Vec::new();
+    for chunk in paths.chunks(200) {
+        let output = Command::new("mdls")
+            .args(["-raw", "-name", "kMDItemLastUsedDate"])
+            .args(chunk)
+            .output()
+            .ok()?;
+        let values = String::from_utf8_lossy(&output.stdout).to_string();
+        for (path, value) in chunk.iter().zip(values.split('\0')) {
+            if let Some(used) = parse_spotlight_date(value) {
+                dated.push((path.clone(), used));
+            }
+        }
+    }
+    
+    dated.sort_by(|a, b| b.1.cmp(&a.1));
+    
+    let results = dated
+        .into_iter()
+        .filter_map(|(path, used)| {
+            let mut result = stat_result(Path::new(&path))?;
+            result.date_value = used;
+            result.date_kind = "Last Opened".to_string();
+            result.score = used;
+            result.pretty_date = format_relative_date(used);
+            Some(result)
+        })
+        .take(n)
+        .collect();
+    Some(results)
+}
+
+/// The `n` most recently modified files in $HOME whose Spotlight creator
+/// (`kMDItemCreator`, e.g. "Pages", "Microsoft Word") contains `app_name`,
+/// case-insensitively. Empty on platforms without Spotlight.
+#[uniffi::export]
+pub fn get_recently_modified_by_app(app_name: String, n: u32) -> Vec<SearchResult> {
+    if app_name.trim().is_empty() {
+        return Vec::new();
+    }
+    
+    #[cfg(target_os = "macos")]
+    {
+        spotlight_files_by_creator(app_name.trim(), n as usize).unwrap_or_default()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = n;
+        Vec::new()
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn spotlight_files_by_creator(app_name: &str, n: usize) -> Option<Vec<SearchResult>> {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    // Quotes and backslashes must be escaped inside a Spotlight query string
+    let escaped = app_name.replace('\\', "\\\\").replace('\'', "\\'");
+    let query = format!("kMDItemCreator == '*{}*'cd", escaped);
+    
+    let output = std::process::Command::new("mdfind")
+        .args(["-0", "-onlyin", &home, &query])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    
+    let mut results: Vec<SearchResult> = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .filter_map(|p| stat_result(Path::new(p)))
+        .collect();
+    sort_newest_first(&mut results);
+    results.truncate(n);
+    Some(results)
+}
+
+// Parse Spotlight's "2024-11-01 10:23:45 +0000" into a unix timestamp
+#[cfg(target_os = "macos")]
+fn parse_spotlight_date(value: &str) -> Option<i64> {
+    let mut parts = value.trim().split(' ');
+    let date = parts.next()?;
+    let time = parts.next()?;
+    let offset = parts.next().unwrap_or("+0000");
+    
+    let mut ymd = date.split('-').map(|p| p.parse::<i64>());
+    let (year, month, day) = (ymd.next()?.ok()?, ymd.next()?.ok()?, ymd.next()?.ok()?);
+    let mut hms = time.split(':').map(|p| p.parse::<i64>());
+    let (hour, minute, second) = (hms.next()?.ok()?, hms.next()?.ok()?, hms.next()?.ok()?);
+    
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let offset_digits = offset.trim_start_matches(['+', '-']);
+    let offset_hours: i64 = offset_digits.get(0..2)?.parse().ok()?;
+    let offset_minutes: i64 = offset_digits.get(2..4)?.parse().ok()?;
+    let offset_secs = sign * (offset_hours * 3600 + offset_minutes * 60);
+    
+    let days = days_from_civil(year, month, day);
+    Some(days * 86400 + hour * 3600 + minute * 60 + second - offset_secs)
+}
+
+// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm)
+#[cfg(target_os = "macos")]
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = (if y >= 0 { y } else { y - 399 }) / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// A Time Machine local snapshot that contains a copy of a file
+#[derive(uniffi::Record, Clone)]
+pub struct FileHistoryEntry {
+    pub snapshot_date: i64,      // Unix timestamp the snapshot was taken
+    pub snapshot_path: String,   // The file's path inside the mounted snapshot
+}
+
+// Only the most recent snapshots are mounted; each mount takes a moment
+#[cfg(target_os = "macos")]
+const MAX_HISTORY_SNAPSHOTS: usize = 10;
+
+// Snapshots mounted by `get_file_history`, until `release_file_history` unmounts them
+static MOUNTED_SNAPSHOTS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Copies of `path` in the 10 most recent Time Machine local snapshots, oldest first.
+/// The snapshots stay mounted read-only so the returned paths can be opened; call
+/// `release_file_history` when done with them. Empty on platforms without Time Machine.
+#[uniffi::export]
+pub fn get_file_history(path: String) -> Result<Vec<FileHistoryEntry>, FileOpError> {
+    #[cfg(target_os = "macos")]
+    {
+        time_machine_history(Path::new(&path))
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = path;
+        Ok(Vec::new())
+    }
+}
+
+/// Unmount the snapshots `get_file_history` mounted. Paths it returned stop working.
+#[uniffi::export]
+pub fn release_file_history() -> FileOpResult {
+    let mounted = MOUNTED_SNAPSHOTS.lock().map(|mut m| std::mem::take(&mut *m)).unwrap_or_default();
+    let mut released = 0;
+    let mut still_mounted = Vec::new();
+    
+    for mount_point in mounted {
+        match std::process::Command::new("umount").arg(&mount_point).status() {
+            Ok(status) if status.success() => {
+                let _ = fs::remove_dir(&mount_point);
+                released += 1;
+            }
+            _ => still_mounted.push(mount_point),
+        }
+    }
+    
+    let failed = still_mounted.len();
+    let message = if failed == 0 {
+        format!("Unmounted {} snapshots", released)
+    } else {
+        let names: Vec<String> = still_mounted.iter().map(|p| p.display().to_string()).collect();
+        format!("Unmounted {} snapshots; could not unmount {}", released, names.join(", "))
+    };
+    // Keep the ones that failed so a later call can retry
+    if let Ok(mut mounted) = MOUNTED_SNAPSHOTS.lock() {
+        mounted.extend(still_mounted);
+    }
+    FileOpResult {
+        success: failed == 0,
+        message,
+        affected_count: released,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn time_machine_history(path: &Path) -> Result<Vec<FileHistoryEntry>, FileOpError> {
+    let output = std::process::Command::new("tmutil").args(["listlocalsnapshots", "/"]).output()?;
+    if !output.status.success() {
+        return Err(FileOpError::Other(format!(
+            "tmutil failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    
+    // Lines look like "com.apple.TimeMachine.2024-11-01-102345.local"
+    let mut snapshots: Vec<(i64, String)> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter_map(|name| Some((parse_snapshot_date(name)?, name.to_string())))
+        .collect();
+    snapshots.sort_by(|a, b| b.0.cmp(&a.0));
+    snapshots.truncate(MAX_HISTORY_SNAPSHOTS);
+    
+    // Time Machine snapshots the data volume; user files live there behind firmlinks
+    let volume = if Path::new("/System/Volumes/Data").is_dir() { "/System/Volumes/Data" } else { "/" };
+    let relative = path.strip_prefix("/").unwrap_or(path);
+    
+    let mut history = Vec::new();
+    for (date, name) in snapshots {
+        let snapshot_path = mount_snapshot(&name, volume)?.join(relative);
+        if fs::symlink_metadata(&snapshot_path).is_ok() {
+            history.push(FileHistoryEntry {
+                snapshot_date: date,
+                snapshot_path: snapshot_path.to_string_lossy().to_string(),
+            });
+        }
+    }
+    history.sort_by_key(|entry| entry.snapshot_date);
+    Ok(history)
+}
+
+// Mount a snapshot read-only under the temp folder, reusing a mount from an earlier call
+#[cfg(target_os = "macos")]
+fn mount_snapshot(name: &str, volume: &str) -> Result<PathBuf, FileOpError> {
+    let mount_point = env::temp_dir().join("fast-finder-snapshots").join(name);
+    let mut mounted = MOUNTED_SNAPSHOTS.lock().map_err(|_| FileOpError::Other("snapshot list is poisoned".to_string()))?;
+    if mounted.contains(&mount_point) {
+        return Ok(mount_point);
+    }
+    fs::create_dir_all(&mount_point)?;
+    
+    let output = std::process::Command::new("mount_apfs")
+        .args(["-o", "rdonly,nobrowse", "-s", name, volume])
+        .arg(&mount_point)
+        .output()?;
+    if !output.status.success() {
+        let _ = fs::remove_dir(&mount_point);
+        return Err(FileOpError::Other(format!(
+            "Failed to mount snapshot {}: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    mounted.push(mount_point.clone());
+    Ok(mount_point)
+}
+
+// Snapshot names carry the local time they were taken: "...TimeMachine.2024-11-01-102345.local"
+#[cfg(target_os = "macos")]
+fn parse_snapshot_date(name: &str) -> Option<i64> {
+    let stamp = name.strip_prefix("com.apple.TimeMachine.")?.split('.').next()?;
+    let mut parts = stamp.split('-');
+    let year: i32 = parts.next()?.parse().ok()?;
+    let month: i32 = parts.next()?.parse().ok()?;
+    let day: i32 = parts.next()?.parse().ok()?;
+    let time = parts.next()?;
+    if time.len() != 6 {
+        return None;
+    }
+    
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    tm.tm_year = year - 1900;
+    tm.tm_mon = month - 1;
+    tm.tm_mday = day;
+    tm.tm_hour = time.get(0..2)?.parse().ok()?;
+    tm.tm_min = time.get(2..4)?.parse().ok()?;
+    tm.tm_sec = time.get(4..6)?.parse().ok()?;
+    tm.tm_isdst = -1;
+    let timestamp = unsafe { libc::mktime(&mut tm) };
+    (timestamp != -1).then_some(timestamp as i64)
+}
+
+/// A named group of results (e.g. one file kind)
+#[derive(uniffi::Record, Clone)]
+pub struct ResultGroup {
+    pub name: String,
+    pub results: Vec<SearchResult>,
+}
+
+/// Recent files grouped by file kind, preceded by an "All" group.
+/// Groups are ordered by their most recent file.
+#[uniffi::export]
+pub fn get_recent_files_grouped(days: u32) -> Vec<ResultGroup> {
+    let recent = recent_files(days);
+    
+    let mut groups: Vec<ResultGroup> = Vec::new();
+    for file in &recent {
+        match groups.iter_mut().find(|g| g.name == file.file_kind) {
+            Some(group) => group.results.push(file.clone()),
+            None => groups.push(ResultGroup {
+                name: file.file_kind.clone(),
+                results: vec![file.clone()],
+            }),
+        }
+    }
+    
+    groups.insert(0, ResultGroup {
+        name: "All".to_string(),
+        results: recent,
+    });
+    groups
+}
+
+// ============== DIRECTORY LISTING ==============
+
+/// List the immediate children of a folder (hidden entries excluded), sorted by name
+#[uniffi::export]
+pub fn list_directory(path: String) -> Vec<SearchResult> {
+    list_directory_sorted(path, SortField::FileName, true, false)
+}
+
+/// List the immediate children of a folder sorted by any field.
+/// Names compare case-insensitively; `show_hidden` includes dotfiles.
+#[uniffi::export]
+pub fn list_directory_sorted(path: String, sort_by: SortField, ascending: bool, show_hidden: bool) -> Vec<SearchResult> {
+    let entries = match fs::read_dir(&path) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let network_mounts = network_mount_points();
+    
+    let mut results: Vec<SearchResult> = entries
+        .flatten()
+        .filter(|entry| show_hidden || !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| {
+            let entry_path = entry.path();
+            let metadata = fs::metadata(&entry_path).ok()?;
+            let mut result = build_result(&entry_path, &FileMetadata::from_std(&metadata));
+            result.is_network_path = is_network_path(&entry_path, &network_mounts);
+            Some(result)
+        })
+        .collect();
+    
+    results.sort_by(|a, b| {
+        let ordering = match sort_by {
+            SortField::FileName => a.file_name.to_lowercase().cmp(&b.file_name.to_lowercase()),
+            field => compare_by_field(a, b, field),
+        };
+        if ascending { ordering } else { ordering.reverse() }
+    });
+    results
+}
+
+// Maximum number of siblings returned with a result's context
+const MAX_CONTEXT_SIBLINGS: usize = 50;
+
+/// A result's surroundings: its containing folder and neighbouring entries
+#[derive(uniffi::Record, Clone)]
+pub struct SearchResultContext {
+    pub siblings: Vec<SearchResult>,
+    pub parent: SearchResult,
+}
+
+/// Fetch the folder containing a result plus up to 50 other entries in it
+#[uniffi::export]
+pub fn get_search_result_context(result: SearchResult) -> SearchResultContext {
+    let file_path = Path::new(&result.file_path);
+    let parent_path = file_path.parent().unwrap_or(file_path);
+    
+    let mut siblings: Vec<SearchResult> = list_directory(parent_path.to_string_lossy().to_string())
+        .into_iter()
+        .filter(|r| r.file_path != result.file_path)
+        .collect();
+    siblings.truncate(MAX_CONTEXT_SIBLINGS);
+    
+    // Fall back to a bare folder entry if the parent can't be stat'd
+    let parent = stat_result(parent_path).unwrap_or_else(|| {
+        build_result(parent_path, &FileMetadata { is_dir: true, ..Default::default() })
+    });
+    
+    SearchResultContext { siblings, parent }
+}
+
+/// Files that appeared, changed or disappeared in a directory
+#[derive(uniffi::Record, Clone, Default)]
+pub struct DirectoryChangeSummary {
+    pub new_files: Vec<SearchResult>,
+    pub modified_files: Vec<SearchResult>,
+    pub deleted_files: Vec<SearchResult>,  // As last seen in the previous snapshot
+}
+
+/// What changed below `path` since the previous call for the same path, which
+/// left a snapshot in the cache. Without a snapshot, files dated at or after
+/// `since_unix` are reported (as new if created then, else modified) and
+/// nothing can be reported as deleted. Each call replaces the snapshot, except
+/// while the encrypted cache is locked.
+#[uniffi::export]
+pub fn get_directory_change_summary(path: String, since_unix: i64) -> DirectoryChangeSummary {
+    let current: Vec<SearchResult> = walkdir::WalkDir::new(&path)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let metadata = e.metadata().ok()?;
+            Some(build_result(e.path(), &FileMetadata::from_std(&metadata)))
+        })
+        .collect();
+    
+    let snapshot_path = snapshot_cache_path(&path);
+    let mut summary = if snapshot_path.exists() {
+        let mut previous: HashMap<String, SearchResult> = load_cache_from(&snapshot_path)
+            .files
+            .into_iter()
+            .map(|f| (f.file_path.clone(), f))
+            .collect();
+        
+        let mut summary = DirectoryChangeSummary::default();
+        for result in &current {
+            match previous.remove(&result.file_path) {
+                None => summary.new_files.push(result.clone()),
+                Some(old) if old.file_size != result.file_size || old.date_value != result.date_value => {
+                    summary.modified_files.push(result.clone());
+                }
+                Some(_) => {}
+            }
+        }
+        summary.deleted_files = previous.into_values().collect();
+        summary
+    } else {
+        let (new_files, modified_files) = current
+            .iter()
+            .filter(|r| r.date_value >= since_unix)
+            .cloned()
+            .partition(|r| r.date_kind == "Created");
+        DirectoryChangeSummary { new_files, modified_files, deleted_files: Vec::new() }
+    };
+    
+    let _ = save_cache_to(&snapshot_path, &FileCache {
+        last_updated: unix_now(),
+        files: current,
+    });
+    
+    sort_newest_first(&mut summary.new_files);
+    sort_newest_first(&mut summary.modified_files);
+    summary.deleted_files.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    summary
+}
+
+// ============== RESULT UTILITIES ==============
+
+/// Merge result lists from multiple searches, keeping the higher-scoring
+/// entry for each path, sorted by score descending
+#[uniffi::export]
+pub fn flatten_search_results(results: Vec<Vec<SearchResult>>) -> Vec<SearchResult> {
+    best_per_path(results.into_iter().flatten())
+}
+
+// The highest-scoring entry for each path, highest first; ties keep their input order
+fn best_per_path(results: impl IntoIterator<Item = SearchResult>) -> Vec<SearchResult> {
+    let mut index_by_path: HashMap<String, usize> = HashMap::new();
+    let mut unique: Vec<SearchResult> = Vec::new();
+    
+    for result in results {
+        match index_by_path.get(&result.file_path) {
+            Some(&i) => {
+                if result.score > unique[i].score {
+                    unique[i] = result;
+                }
+            }
+            None => {
+                index_by_path.insert(result.file_path.clone(), unique.len());
+                unique.push(result);
+            }
+        }
+    }
+    
+    unique.sort_by_key(|r| Reverse(r.score));
+    unique
+}
+
+/// Keep only paths present in both lists (AND-style), summing their scores
+#[uniffi::export]
+pub fn intersect_search_results(a: Vec<SearchResult>, b: Vec<SearchResult>) -> Vec<SearchResult> {
+    let mut b_scores: HashMap<String, i64> = HashMap::new();
+    for result in b {
+        let best = b_scores.entry(result.file_path).or_insert(result.score);
+        *best = (*best).max(result.score);
+    }
+    
+    let mut by_path: HashMap<String, SearchResult> = HashMap::new();
+    for mut result in a {
+        if let Some(b_score) = b_scores.get(&result.file_path) {
+            result.score += b_score;
+            match by_path.get(&result.file_path) {
+                Some(existing) if existing.score >= result.score => {}
+                _ => {
+                    by_path.insert(result.file_path.clone(), result);
+                }
+            }
+        }
+    }
+    
+    let mut intersected: Vec<SearchResult> = by_path.into_values().collect();
+    intersected.sort_by_key(|r| Reverse(r.score));
+    intersected
+}
+
+/// Remove repeated paths from merged result lists, keeping the highest-scoring
+/// entry for each. Sorted by score, highest first; ties keep their input order.
+#[uniffi::export]
+pub fn deduplicate_results(results: Vec<SearchResult>) -> Vec<SearchResult> {
+    best_per_path(results)
+}
+
+/// Lowest and highest score in a result list
+#[derive(uniffi::Record, Clone, Copy, Default)]
+pub struct ScoreRange {
+    pub min_score: i64,
+    pub max_score: i64,
+}
+
+/// The raw score range of `results`, for calibrating `normalize_scores`. Zeros when empty.
+#[uniffi::export]
+pub fn get_raw_score_range(results: Vec<SearchResult>) -> ScoreRange {
+    score_range(&results).unwrap_or_default()
+}
+
+fn score_range(results: &[SearchResult]) -> Option<ScoreRange> {
+    let min_score = results.iter().map(|r| r.score).min()?;
+    let max_score = results.iter().map(|r| r.score).max()?;
+    Some(ScoreRange { min_score, max_score })
+}
+
+/// Rescale `score` linearly from the list's [min, max] to 0–100 for display as a
+/// confidence percentage. When every score is equal, all become 100.
+#[uniffi::export]
+pub fn normalize_scores(mut results: Vec<SearchResult>) -> Vec<SearchResult> {
+    let Some(ScoreRange { min_score, max_score }) = score_range(&results) else {
+        return results;
+    };
+    let span = (max_score - min_score) as f64;
+    for result in &mut results {
+        result.score = if span == 0.0 {
+            100
+        } else {
+            ((result.score - min_score) as f64 / span * 100.0).round() as i64
+        };
+    }
+    results
+}
+
+/// What differs between two snapshots of the same file
+#[derive(uniffi::Record, Clone)]
+pub struct FileDelta {
+    pub size_changed: bool,
+    pub name_changed: bool,
+    pub path_changed: bool,  // Moved or renamed
+    pub date_changed: bool,
+    pub old_size: u64,
+    pub new_size: u64,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+/// Compare two results for the same file, e.g. before and after a file operation
+#[uniffi::export]
+pub fn get_file_delta(old_result: SearchResult, new_result: SearchResult) -> FileDelta {
+    FileDelta {
+        size_changed: old_result.file_size != new_result.file_size,
+        name_changed: old_result.file_name != new_result.file_name,
+        path_changed: old_result.file_path != new_result.file_path,
+        date_changed: old_result.date_value != new_result.date_value,
+        old_size: old_result.file_size,
+        new_size: new_result.file_size,
+        old_name: old_result.file_name,
+        new_name: new_result.file_name,
+    }
+}
+
+/// Groups of cached files with identical contents, largest groups first.
+/// Reads only the cache, so it needs an index built with
+/// `compute_checksums_during_index`; files without a checksum are ignored.
+#[uniffi::export]
+pub fn find_duplicates() -> Vec<ResultGroup> {
+    let mut by_checksum: HashMap<String, Vec<SearchResult>> = HashMap::new();
+    for result in load_cache().files {
+        if !result.checksum.is_empty() {
+            by_checksum.entry(result.checksum.clone()).or_default().push(result);
+        }
+    }
+    
+    let mut groups: Vec<ResultGroup> = by_checksum
+        .into_iter()
+        .filter(|(_, results)| results.len() > 1)
+        .map(|(checksum, mut results)| {
+            results.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+            ResultGroup { name: checksum, results }
+        })
+        .collect();
+    groups.sort_by(|a, b| b.results.len().cmp(&a.results.len()).then_with(|| a.name.cmp(&b.name)));
+    groups
+}
+
+/// Results that share a parent folder
+#[derive(uniffi::Record, Clone)]
+pub struct DirGroup {
+    pub directory: String,
+    pub files: Vec<SearchResult>,
+}
+
+/// Group results by parent folder. Groups are sorted by folder path,
+/// files within a group by score (highest first).
+#[uniffi::export]
+pub fn group_by_parent_directory(results: Vec<SearchResult>) -> Vec<DirGroup> {
+    let mut by_directory: HashMap<String, Vec<SearchResult>> = HashMap::new();
+    for result in results {
+        let directory = Path::new(&result.file_path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        by_directory.entry(directory).or_default().push(result);
+    }
+    
+    let mut groups: Vec<DirGroup> = by_directory
+        .into_iter()
+        .map(|(directory, mut files)| {
+            files.sort_by_key(|f| Reverse(f.score));
+            DirGroup { directory, files }
+        })
+        .collect();
+    groups.sort_by(|a, b| a.directory.cmp(&b.directory));
+    groups
+}
+
+// `SearchResult` fields that can be exported as table columns
+const RESULT_COLUMNS: &[&str] = &[
+    "file_name", "file_path", "file_size", "is_folder", "score", "date_value",
+    "date_kind", "file_kind", "pretty_date", "is_network_path", "is_alias", "checksum",
+    "page_count",
+];
+
+// Text of a result field by name (one of `RESULT_COLUMNS`)
+fn result_column_value(result: &SearchResult, column: &str) -> String {
+    match column {
+        "file_name" => result.file_name.clone(),
+        "file_path" => result.file_path.clone(),
+        "file_size" => result.file_size.to_string(),
+        "is_folder" => result.is_folder.to_string(),
+        "score" => result.score.to_string(),
+        "date_value" => result.date_value.to_string(),
+        "date_kind" => result.date_kind.clone(),
+        "file_kind" => result.file_kind.clone(),
+        "pretty_date" => result.pretty_date.clone(),
+        "is_network_path" => result.is_network_path.to_string(),
+        "is_alias" => result.is_alias.to_string(),
+        "checksum" => result.checksum.clone(),
+        "page_count" => result.page_count.map(|n| n.to_string()).unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Render results as a GitHub Markdown table with one column per entry of
+/// `columns` (`SearchResult` field names, e.g. "file_name", "file_size").
+/// Unknown column names are ignored; returns "" if none are left.
+#[uniffi::export]
+pub fn export_results_as_markdown_table(results: Vec<SearchResult>, columns: Vec<String>) -> String {
+    let columns: Vec<&str> = columns
+        .iter()
+        .map(|c| c.trim())
+        .filter(|c| RESULT_COLUMNS.contains(c))
+        .collect();
+    if columns.is_empty() {
+        return String::new();
+    }
+    
+    // Pipes would end the cell and newlines the row
+    let escape = |value: &str| value.replace('|', "\\|").replace(['\r', '\n'], " ");
+    
+    let mut table = format!("| {} |\n", columns.join(" | "));
+    table.push_str(&format!("|{}\n", " --- |".repeat(columns.len())));
+    for result in &results {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|c| escape(&result_column_value(result, c)))
+            .collect();
+        table.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    table
+}
+
+/// Layout for `format_results_as_text`
+#[derive(uniffi::Enum, Clone, Copy)]
+pub enum ResultTextFormat {
+    /// One `format_result_as_text` line per result
+    Plain,
+    /// Tab-separated with a header row
+    Tsv,
+    /// Comma-separated (RFC 4180 quoting) with a header row
+    Csv,
+}
+
+/// A single clipboard-friendly line, e.g.
+/// "report.pdf  ~/Documents/Q4  1.2 MB  Modified 2024-11-01"
+#[uniffi::export]
+pub fn format_result_as_text(result: SearchResult) -> String {
+    let mut fields = vec![result.file_name.clone(), display_folder(&result.file_path)];
+    if !result.is_folder {
+        fields.push(format_size(result.file_size));
+    }
+    fields.push(format!("{} {}", result.date_kind, format_date(result.date_value)));
+    fields.join("  ")
+}
+
+/// Results as text for the clipboard, one line per result
+#[uniffi::export]
+pub fn format_results_as_text(results: Vec<SearchResult>, format: ResultTextFormat) -> String {
+    let (separator, escape): (&str, fn(&str) -> String) = match format {
+        ResultTextFormat::Plain => {
+            return results.into_iter().map(|r| format_result_as_text(r) + "\n").collect();
+        }
+        ResultTextFormat::Tsv => ("\t", |field: &str| field.replace(['\t', '\r', '\n'], " ")),
+        ResultTextFormat::Csv => (",", |field: &str| {
+            if field.contains([',', '"', '\r', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        }),
+    };
+    
+    let mut text = ["Name", "Folder", "Size", "Date Kind", "Date"].join(separator);
+    text.push('\n');
+    for result in &results {
+        let size = if result.is_folder { String::new() } else { format_size(result.file_size) };
+        let fields = [
+            result.file_name.clone(),
+            display_folder(&result.file_path),
+            size,
+            result.date_kind.clone(),
+            format_date(result.date_value),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| escape(f)).collect();
+        text.push_str(&row.join(separator));
+        text.push('\n');
+    }
+    text
+}
+
+// Parent folder of a path, with the home directory shortened to "~"
+fn display_folder(file_path: &str) -> String {
+    let parent = Path::new(file_path).parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+    match env::var("HOME") {
+        Ok(home) if !home.is_empty() && Path::new(&parent).starts_with(&home) => {
+            format!("~{}", &parent[home.trim_end_matches('/').len()..])
+        }
+        _ => parent,
+    }
+}
+
+// Decimal units like Finder: "532 bytes", "1.2 MB"
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KB", "MB", "GB", "TB", "PB"];
+    if bytes < 1000 {
+        return format!("{} bytes", bytes);
+    }
+    let mut value = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+// "2024-11-01" (UTC) for a unix timestamp
+fn format_date(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// Proleptic Gregorian date for days since 1970-01-01 (Howard Hinnant's algorithm)
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// ============== FILE INFO ==============
+
+/// Low-level stat fields beyond what `SearchResult` carries
+#[derive(uniffi::Record, Clone, Default)]
+pub struct ExtendedMetadata {
+    pub inode: u64,
+    pub hard_link_count: u32,
+    pub block_size: u64,
+    pub block_count: u64,
+    pub uid: u32,
+    pub gid: u32,
+    pub permissions_octal: String,
+    pub file_flags: u32,  // BSD flags such as UF_HIDDEN (macOS only)
+    pub acl: String,      // Textual ACL, empty if none or unsupported
+}
+
+/// All stat fields available for a path. Returns zeroed fields if it can't be read.
+#[uniffi::export]
+pub fn get_file_metadata_extended(path: String) -> ExtendedMetadata {
+    match fs::symlink_metadata(&path) {
+        Ok(metadata) => extended_metadata(Path::new(&path), &metadata),
+        Err(_) => ExtendedMetadata::default(),
+    }
+}
+
+/// `ls -l` style permission string such as "-rw-r--r--" or "drwxr-xr-x".
+/// Returns "?---------" if the path can't be read or on Windows.
+#[uniffi::export]
+pub fn get_file_permissions_string(path: String) -> String {
+    match fs::symlink_metadata(&path) {
+        Ok(metadata) => permissions_string(&metadata),
+        Err(_) => "?---------".to_string(),
+    }
+}
+
+#[cfg(unix)]
+fn permissions_string(metadata: &fs::Metadata) -> String {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    
+    let file_type = metadata.file_type();
+    let type_char = if file_type.is_dir() {
+        'd'
+    } else if file_type.is_symlink() {
+        'l'
+    } else if file_type.is_fifo() {
+        'p'
+    } else if file_type.is_socket() {
+        's'
+    } else if file_type.is_char_device() {
+        'c'
+    } else if file_type.is_block_device() {
+        'b'
+    } else {
+        '-'
+    };
+    
+    let mode = metadata.permissions().mode();
+    let mut out = String::with_capacity(10);
+    out.push(type_char);
+    // (read, write, execute bit, special bit, special char) for owner, group, other
+    for (read, write, exec, special, special_char) in [
+        (0o400, 0o200, 0o100, 0o4000, 's'),  // setuid
+        (0o040, 0o020, 0o010, 0o2000, 's'),  // setgid
+        (0o004, 0o002, 0o001, 0o1000, 't'),  // sticky
+    ] {
+        out.push(if mode & read != 0 { 'r' } else { '-' });
+        out.push(if mode & write != 0 { 'w' } else { '-' });
+        out.push(match (mode & exec != 0, mode & special != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    out
+}
+
+#[cfg(not(unix))]
+fn permissions_string(_metadata: &fs::Metadata) -> String {
+    "?---------".to_string()
+}
+
+/// Aggregate figures for a folder's contents, for Info panels
+#[derive(uniffi::Record, Clone, Default)]
+pub struct FolderSummary {
+    pub total_size: u64,
+    pub file_count: u32,
+    pub folder_count: u32,
+    pub oldest_date: i64,          // 0 if the folder is empty
+    pub newest_date: i64,          // 0 if the folder is empty
+    pub most_common_kind: String,  // Among files; empty if there are none
+}
+
+/// Summarize the entries directly inside `path`, or everything below it when `deep` is set.
+/// Unreadable entries are skipped.
+#[uniffi::export]
+pub fn get_folder_contents_summary(path: String, deep: bool) -> FolderSummary {
+    let mut walker = walkdir::WalkDir::new(&path).min_depth(1);
+    if !deep {
+        walker = walker.max_depth(1);
+    }
+    
+    let mut summary = FolderSummary::default();
+    let mut kind_counts: HashMap<String, u32> = HashMap::new();
+    let mut dates: Option<(i64, i64)> = None;
+    
+    for entry in walker.into_iter().filter_map(|e| e.ok()) {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let is_folder = metadata.is_dir();
+        
+        if is_folder {
+            summary.folder_count += 1;
+        } else {
+            summary.file_count += 1;
+            summary.total_size += metadata.len();
+            *kind_counts.entry(get_file_kind(entry.path(), false)).or_insert(0) += 1;
+        }
+        
+        let (date, _) = get_best_date(&FileMetadata::from_std(&metadata));
+        dates = Some(match dates {
+            Some((oldest, newest)) => (oldest.min(date), newest.max(date)),
+            None => (date, date),
+        });
+    }
+    
+    if let Some((oldest, newest)) = dates {
+        summary.oldest_date = oldest;
+        summary.newest_date = newest;
+    }
+    // Ties go to the alphabetically first kind so the answer is stable
+    summary.most_common_kind = kind_counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .map(|(kind, _)| kind)
+        .unwrap_or_default();
+    summary
+}
+
+/// Files in one age range of `get_directory_age_distribution`
+#[derive(uniffi::Record, Clone)]
+pub struct AgeBucket {
+    pub label: String,
+    pub count: u32,
+    pub total_size_bytes: u64,
+}
+
+// Bucket labels and their upper age bound in days (the last one is open-ended)
+const AGE_BUCKETS: &[(&str, i64)] = &[
+    ("0–7 days", 7),
+    ("7–30 days", 30),
+    ("30–365 days", 365),
+    ("1–3 years", 3 * 365),
+    ("3+ years", i64::MAX),
+];
+
+/// Histogram of the ages of all files below `path`, by the same date `date_value` uses.
+/// Always returns every bucket, youngest first, even when empty.
+#[uniffi::export]
+pub fn get_directory_age_distribution(path: String) -> Vec<AgeBucket> {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let mut buckets: Vec<AgeBucket> = AGE_BUCKETS
+        .iter()
+        .map(|(label, _)| AgeBucket { label: label.to_string(), count: 0, total_size_bytes: 0 })
+        .collect();
+    
+    for entry in walkdir::WalkDir::new(&path).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        
+        let (date, _) = get_best_date(&FileMetadata::from_std(&metadata));
+        let age_days = (now - date).max(0) / 86400;
+        let index = AGE_BUCKETS.iter().position(|&(_, max_days)| age_days < max_days).unwrap_or(AGE_BUCKETS.len() - 1);
+        buckets[index].count += 1;
+        buckets[index].total_size_bytes += metadata.len();
+    }
+    buckets
+}
+
+/// How many files share an extension, and their combined size
+#[derive(uniffi::Record, Clone)]
+pub struct ExtensionStat {
+    pub extension: String,  // Lowercased, without the dot
+    pub file_count: u32,
+    pub total_size: u64,
+}
+
+/// The `n` most common file extensions below `root`, by file count. Walks the
+/// tree directly (single-threaded), so it works without an up-to-date index.
+/// Files without an extension are not counted.
+#[uniffi::export]
+pub fn get_top_file_extensions(root: String, n: u32) -> Vec<ExtensionStat> {
+    let mut by_extension: HashMap<String, ExtensionStat> = HashMap::new();
+    
+    for entry in walkdir::WalkDir::new(&root).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Some(ext) = entry.path().extension() else {
+            continue;
+        };
+        let extension = ext.to_string_lossy().to_lowercase();
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        
+        let stat = by_extension.entry(extension.clone()).or_insert(ExtensionStat {
+            extension,
+            file_count: 0,
+            total_size: 0,
+        });
+        stat.file_count += 1;
+        stat.total_size += size;
+    }
+    
+    let mut stats: Vec<ExtensionStat> = by_extension.into_values().collect();
+    stats.sort_by(|a, b| b.file_count.cmp(&a.file_count).then_with(|| a.extension.cmp(&b.extension)));
+    stats.truncate(n as usize);
+    stats
+}
+
+#[cfg(unix)]
+fn extended_metadata(path: &Path, metadata: &fs::Metadata) -> ExtendedMetadata {
+    use std::os::unix::fs::MetadataExt;
+    
+    #[cfg(target_os = "macos")]
+    let file_flags = std::os::macos::fs::MetadataExt::st_flags(metadata);
+    #[cfg(not(target_os = "macos"))]
+    let file_flags = 0;
+    
+    ExtendedMetadata {
+        inode: metadata.ino(),
+        hard_link_count: metadata.nlink() as u32,
+        block_size: metadata.blksize(),
+        block_count: metadata.blocks(),
+        uid: metadata.uid(),
+        gid: metadata.gid(),
+        permissions_octal: format!("{:04o}", metadata.mode() & 0o7777),
+        file_flags,
+        acl: read_acl(path),
+    }
+}
+
+#[cfg(not(unix))]
+fn extended_metadata(_path: &Path, _metadata: &fs::Metadata) -> ExtendedMetadata {
+    ExtendedMetadata::default()
+}
+
+// `ls -le` prints ACL entries on the lines after the listing itself
+#[cfg(target_os = "macos")]
+fn read_acl(path: &Path) -> String {
+    std::process::Command::new("ls")
+        .arg("-led")
+        .arg(path)
+        .output()
+        .map(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .skip(1)
+                .map(|line| line.trim())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn read_acl(path: &Path) -> String {
+    std::process::Command::new("getfacl")
+        .args(["--omit-header", "--absolute-names"])
+        .arg(path)
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+// ============== FILE CONTENTS ==============
+
+/// Lines `from_line..=to_line` (1-based) of a text file, joined by newlines.
+/// Returns an empty string if the range is invalid or starts past the end of the file;
+/// a range running past the end returns the lines that exist.
+#[uniffi::export]
+pub fn get_file_line_range(path: String, from_line: u32, to_line: u32) -> String {
+    if from_line == 0 || to_line < from_line {
+        return String::new();
+    }
+    
+    let file = match fs::File::open(&path) {
+        Ok(f) => f,
+        Err(_) => return String::new(),
+    };
+    
+    let lines: Vec<String> = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .skip((from_line - 1) as usize)
+        .take((to_line - from_line + 1) as usize)
+        .collect();
+    
+    lines.join("\n")
+}
+
+/// How two files' contents compare
+#[derive(uniffi::Record, Clone, Default)]
+pub struct FileDiff {
+    pub are_identical: bool,
+    pub size_a: u64,
+    pub size_b: u64,
+    pub first_differing_byte_offset: Option<u64>,
+    /// For text files, the number of lines before the first difference
+    pub differing_line_count: Option<u32>,
+}
+
+/// Compare two files in 64 KB chunks, stopping at the first difference.
+/// Unreadable files are reported as not identical.
+#[uniffi::export]
+pub fn compare_files(path_a: String, path_b: String) -> FileDiff {
+    const CHUNK: usize = 64 * 1024;
+    
+    let (mut file_a, mut file_b) = match (fs::File::open(&path_a), fs::File::open(&path_b)) {
+        (Ok(a), Ok(b)) => (a, b),
+        _ => return FileDiff::default(),
+    };
+    let size_a = file_a.metadata().map(|m| m.len()).unwrap_or(0);
+    let size_b = file_b.metadata().map(|m| m.len()).unwrap_or(0);
+    
+    let mut buf_a = vec![0u8; CHUNK];
+    let mut buf_b = vec![0u8; CHUNK];
+    let mut offset: u64 = 0;
+    let mut newlines: u32 = 0;
+    let mut is_text = true;
+    
+    loop {
+        let (read_a, read_b) = match (read_full(&mut file_a, &mut buf_a), read_full(&mut file_b, &mut buf_b)) {
+            (Ok(a), Ok(b)) => (a, b),
+            _ => return FileDiff { size_a, size_b, ..Default::default() },
+        };
+        
+        let common = read_a.min(read_b);
+        let mismatch = buf_a[..common]
+            .iter()
+            .zip(&buf_b[..common])
+            .position(|(a, b)| a != b)
+            .or((read_a != read_b).then_some(common));
+        
+        // Only the bytes before the difference count towards the line number
+        let scanned = &buf_a[..mismatch.unwrap_or(read_a)];
+        is_text &= !scanned.contains(&0);
+        newlines += scanned.iter().filter(|&&b| b == b'\n').count() as u32;
+        
+        if let Some(index) = mismatch {
+            return FileDiff {
+                are_identical: false,
+                size_a,
+                size_b,
+                first_differing_byte_offset: Some(offset + index as u64),
+                differing_line_count: is_text.then_some(newlines),
+            };
+        }
+        if read_a == 0 {
+            return FileDiff {
+                are_identical: true,
+                size_a,
+                size_b,
+                first_differing_byte_offset: None,
+                differing_line_count: None,
+            };
+        }
+        offset += read_a as u64;
+    }
+}
+
+// Fill `buf` as far as possible, returning fewer bytes only at end of file
+fn read_full(reader: &mut impl io::Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Content hash of a directory tree that ignores metadata: every file's
+/// relative path and content hash (`algorithm`: "sha256" or "sha512"), in
+/// sorted order, hashed together with SHA-256. Identical trees give identical
+/// hashes wherever they live. Returns an empty string on unknown algorithms
+/// or unreadable files.
+#[uniffi::export]
+pub fn hash_directory(path: String, algorithm: String) -> String {
+    use sha2::{Digest, Sha256};
+    
+    let algorithm = algorithm.to_lowercase();
+    if !matches!(algorithm.as_str(), "sha256" | "sha512") {
+        return String::new();
+    }
+    
+    let root = Path::new(&path);
+    let mut combined = Sha256::new();
+    
+    for entry in walkdir::WalkDir::new(root).sort_by_file_name() {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => return String::new(),
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        
+        // Relative, '/'-separated paths so the hash doesn't depend on the root location
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        
+        let file_hash = match hash_file(entry.path(), &algorithm) {
+            Some(h) => h,
+            None => return String::new(),
+        };
+        
+        combined.update(relative.as_bytes());
+        combined.update([0u8]);
+        combined.update(file_hash.as_bytes());
+        combined.update([b'\n']);
+    }
+    
+    format!("{:x}", combined.finalize())
+}
+
+// Hex digest of a file's contents, streamed through the hasher
+fn hash_file(path: &Path, algorithm: &str) -> Option<String> {
+    use sha2::{Digest, Sha256, Sha512};
+    
+    let mut file = fs::File::open(path).ok()?;
+    match algorithm {
+        "sha512" => {
+            let mut hasher = Sha512::new();
+            io::copy(&mut file, &mut hasher).ok()?;
+            Some(format!("{:x}", hasher.finalize()))
+        }
+        _ => {
+            let mut hasher = Sha256::new();
+            io::copy(&mut file, &mut hasher).ok()?;
+            Some(format!("{:x}", hasher.finalize()))
+        }
+    }
+}
+
+/// The file's contents as standard (padded) base64, or "" if it can't be read.
+/// The file is read in chunks rather than loaded whole.
+#[uniffi::export]
+pub fn encode_base64_file(path: String) -> String {
+    use base64::Engine;
+    
+    // A multiple of 3 so chunks encode without padding in between
+    const CHUNK_SIZE: usize = 3 * 16 * 1024;
+    
+    let Ok(mut file) = fs::File::open(&path) else {
+        return String::new();
+    };
+    let capacity = file.metadata().map(|m| m.len() as usize / 3 * 4 + 4).unwrap_or(0);
+    let mut encoded = String::with_capacity(capacity);
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    
+    loop {
+        match read_full(&mut file, &mut buf) {
+            Ok(0) => break,
+            Ok(n) => base64::engine::general_purpose::STANDARD.encode_string(&buf[..n], &mut encoded),
+            Err(_) => return String::new(),
+        }
+    }
+    encoded
+}
+
+/// Decode standard base64 (whitespace and a `data:...;base64,` prefix are
+/// allowed) and write the bytes to `destination`, decoding chunk by chunk
+#[uniffi::export]
+pub fn decode_base64_to_file(data: String, destination: String) -> FileOpResult {
+    match decode_base64_into(&data, Path::new(&destination)) {
+        Ok(written) => FileOpResult {
+            success: true,
+            message: format!("Wrote {} bytes", written),
+            affected_count: 1,
+        },
+        Err(e) => {
+            let _ = fs::remove_file(&destination);
+            FileOpResult {
+                success: false,
+                message: format!("Failed to decode: {}", e),
+                affected_count: 0,
+            }
+        }
+    }
+}
+
+fn decode_base64_into(data: &str, destination: &Path) -> io::Result<u64> {
+    use base64::Engine;
+    
+    // A multiple of 4 so only the last chunk can contain padding
+    const CHUNK_SIZE: usize = 4 * 16 * 1024;
+    
+    let data = match data.split_once(";base64,") {
+        Some((prefix, payload)) if prefix.starts_with("data:") => payload,
+        _ => data,
+    };
+    
+    let mut writer = BufWriter::new(fs::File::create(destination)?);
+    let mut pending: Vec<u8> = Vec::with_capacity(CHUNK_SIZE);
+    let mut decoded = vec![0u8; CHUNK_SIZE / 4 * 3];
+    let mut written = 0u64;
+    
+    let mut flush_chunk = |pending: &mut Vec<u8>, writer: &mut BufWriter<fs::File>| -> io::Result<()> {
+        let n = base64::engine::general_purpose::STANDARD
+            .decode_slice(pending.as_slice(), &mut decoded)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writer.write_all(&decoded[..n])?;
+        written += n as u64;
+        pending.clear();
+        Ok(())
+    };
+    
+    for byte in data.bytes().filter(|b| !b.is_ascii_whitespace()) {
+        pending.push(byte);
+        if pending.len() == CHUNK_SIZE {
+            flush_chunk(&mut pending, &mut writer)?;
+        }
+    }
+    if !pending.is_empty() {
+        flush_chunk(&mut pending, &mut writer)?;
+    }
+    writer.flush()?;
+    Ok(written)
+}
+
+// ============== PATH UTILITIES ==============
+
+// Everything except unreserved characters and '/' is escaped in file URLs
+const URL_PATH_ENCODE_SET: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Turn an absolute path into a `file://` URL, e.g.
+/// `/Users/alice/My File.pdf` -> `file:///Users/alice/My%20File.pdf`
+#[uniffi::export]
+pub fn encode_path_for_url(path: String) -> String {
+    let encoded = percent_encoding::utf8_percent_encode(&path, URL_PATH_ENCODE_SET);
+    if path.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        format!("file:///{}", encoded)
+    }
+}
+
+/// Extract the path from a `file://` URL (as used by drag-and-drop and the pasteboard).
+/// Returns an empty string for other URL schemes.
+#[uniffi::export]
+pub fn decode_url_to_path(url: String) -> String {
+    let rest = match url.strip_prefix("file://") {
+        Some(rest) => rest,
+        None => return String::new(),
+    };
+    // `file://localhost/path` is equivalent to `file:///path`
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    
+    percent_encoding::percent_decode_str(rest).decode_utf8_lossy().to_string()
+}
+
+// ============== DISK SPACE ==============
+
+/// Space on the volume containing a path, in bytes
+#[derive(uniffi::Record, Clone, Default)]
+pub struct DiskSpace {
+    pub available_bytes: u64,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+}
+
+/// Space on the volume containing `path`, for checking before large copies.
+/// None if the volume can't be queried (missing path, unsupported platform).
+#[uniffi::export]
+pub fn get_available_disk_space(path: String) -> Option<DiskSpace> {
+    disk_space(Path::new(&path))
+}
+
+#[cfg(unix)]
+fn disk_space(path: &Path) -> Option<DiskSpace> {
+    use std::os::unix::ffi::OsStrExt;
+    
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    
+    let block_size = stat.f_frsize as u64;
+    let total = stat.f_blocks as u64 * block_size;
+    let free = stat.f_bfree as u64 * block_size;
+    Some(DiskSpace {
+        available_bytes: stat.f_bavail as u64 * block_size,
+        total_bytes: total,
+        used_bytes: total.saturating_sub(free),
+    })
+}
+
+#[cfg(windows)]
+fn disk_space(path: &Path) -> Option<DiskSpace> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+    
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    let (mut available, mut total, mut free) = (0u64, 0u64, 0u64);
+    let ok = unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, &mut total, &mut free) };
+    if ok == 0 {
+        return None;
+    }
+    
+    Some(DiskSpace {
+        available_bytes: available,
+        total_bytes: total,
+        used_bytes: total.saturating_sub(free),
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+fn disk_space(_path: &Path) -> Option<DiskSpace> {
+    None
+}
+
+/// A mounted volume, for choosing which locations to scan
+#[derive(uniffi::Record, Clone)]
+pub struct VolumeInfo {
+    pub name: String,
+    pub mount_point: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,     // Available to the current user
+    pub is_network: bool,
+    pub is_removable: bool,
+    pub file_system: String, // e.g. "apfs", "ext4", "NTFS"
+}
+
+/// Volumes the user can browse. System-only and pseudo file systems
+/// (/proc, tmpfs, macOS's hidden system volumes, ...) are left out.
+#[uniffi::export]
+pub fn list_mounted_volumes() -> Vec<VolumeInfo> {
+    mounted_volumes()
+}
+
+// Last component of the mount point, or the mount point itself for "/"
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn volume_name(mount_point: &str) -> String {
+    Path::new(mount_point)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| mount_point.to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn mounted_volumes() -> Vec<VolumeInfo> {
+    use std::ffi::CStr;
+    
+    // From <sys/mount.h>
+    const MNT_REMOVABLE: u64 = 0x0000_0200;
+    const MNT_DONTBROWSE: u64 = 0x0010_0000;
+    
+    let mut mounts: *mut libc::statfs = std::ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut mounts, libc::MNT_NOWAIT) };
+    if count <= 0 || mounts.is_null() {
+        return Vec::new();
+    }
+    
+    let mounts = unsafe { std::slice::from_raw_parts(mounts, count as usize) };
+    mounts
+        .iter()
+        .filter(|m| (m.f_flags as u64) & MNT_DONTBROWSE == 0)
+        .map(|m| {
+            let flags = m.f_flags as u64;
+            let mount_point = unsafe { CStr::from_ptr(m.f_mntonname.as_ptr()) }.to_string_lossy().to_string();
+            let file_system = unsafe { CStr::from_ptr(m.f_fstypename.as_ptr()) }.to_string_lossy().to_string();
+            let block_size = m.f_bsize as u64;
+            VolumeInfo {
+                name: volume_name(&mount_point),
+                total_bytes: m.f_blocks as u64 * block_size,
+                free_bytes: m.f_bavail as u64 * block_size,
+                is_network: flags & (libc::MNT_LOCAL as u64) == 0,
+                is_removable: flags & MNT_REMOVABLE != 0,
+                file_system,
+                mount_point,
+            }
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn mounted_volumes() -> Vec<VolumeInfo> {
+    let mounts = fs::read_to_string("/proc/mounts").unwrap_or_default();
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let mount_point = unescape_mount_path(fields.next()?);
+            let file_system = fields.next()?.to_string();
+            let is_network = NETWORK_FS_TYPES.contains(&file_system.as_str());
+            
+            // Pseudo file systems have no backing device; loop devices are snaps and images
+            if !is_network && (!device.starts_with("/dev/") || device.starts_with("/dev/loop")) {
+                return None;
+            }
+            
+            let space = disk_space(Path::new(&mount_point)).unwrap_or_default();
+            Some(VolumeInfo {
+                name: volume_name(&mount_point),
+                total_bytes: space.total_bytes,
+                free_bytes: space.available_bytes,
+                is_network,
+                is_removable: !is_network && is_removable_device(device),
+                file_system,
+                mount_point,
+            })
+        })
+        .collect()
+}
+
+// The kernel flags removable media on the whole disk, so a partition (sdb1) checks its parent (sdb)
+#[cfg(target_os = "linux")]
+fn is_removable_device(device: &str) -> bool {
+    // Resolves /dev/disk/by-uuid/... and /dev/mapper/... symlinks
+    let Ok(resolved) = fs::canonicalize(device) else {
+        return false;
+    };
+    let Some(name) = resolved.file_name() else {
+        return false;
+    };
+    
+    let sys_dir = Path::new("/sys/class/block").join(name);
+    fs::read_to_string(sys_dir.join("removable"))
+        .or_else(|_| fs::read_to_string(sys_dir.join("../removable")))
+        .map(|flag| flag.trim() == "1")
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn mounted_volumes() -> Vec<VolumeInfo> {
+    use windows_sys::Win32::Storage::FileSystem::{GetDriveTypeW, GetLogicalDrives, GetVolumeInformationW};
+    
+    // From <WinBase.h>
+    const DRIVE_REMOVABLE: u32 = 2;
+    const DRIVE_REMOTE: u32 = 4;
+    
+    let from_wide = |buf: &[u16]| {
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+        String::from_utf16_lossy(&buf[..len])
+    };
+    
+    let drive_mask = unsafe { GetLogicalDrives() };
+    (0..26u8)
+        .filter(|i| drive_mask & (1 << i) != 0)
+        .map(|i| {
+            let mount_point = format!("{}:\\", (b'A' + i) as char);
+            let wide: Vec<u16> = mount_point.encode_utf16().chain(std::iter::once(0)).collect();
+            let mut label = [0u16; 261];
+            let mut file_system = [0u16; 261];
+            let ok = unsafe {
+                GetVolumeInformationW(
+                    wide.as_ptr(),
+                    label.as_mut_ptr(),
+                    label.len() as u32,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    file_system.as_mut_ptr(),
+                    file_system.len() as u32,
+                )
+            } != 0;
+            let drive_type = unsafe { GetDriveTypeW(wide.as_ptr()) };
+            let space = disk_space(Path::new(&mount_point)).unwrap_or_default();
+            
+            let label = if ok { from_wide(&label) } else { String::new() };
+            VolumeInfo {
+                name: if label.is_empty() { mount_point.clone() } else { label },
+                total_bytes: space.total_bytes,
+                free_bytes: space.available_bytes,
+                is_network: drive_type == DRIVE_REMOTE,
+                is_removable: drive_type == DRIVE_REMOVABLE,
+                file_system: if ok { from_wide(&file_system) } else { String::new() },
+                mount_point,
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn mounted_volumes() -> Vec<VolumeInfo> {
+    Vec::new()
+}
+
+/// The `n` largest files of at least `min_size_bytes` below `root`, largest first.
+/// Hidden and ignored files are included since they take up space too.
+#[uniffi::export]
+pub fn find_large_files(root: String, min_size_bytes: u64, n: u32) -> Vec<SearchResult> {
+    use std::collections::BinaryHeap;
+    
+    // Min-heap of (size, path) for the largest files so far, so memory stays O(n)
+    type LargestFiles = BinaryHeap<Reverse<(u64, String)>>;
+    
+    let limit = n as usize;
+    if limit == 0 {
+        return Vec::new();
+    }
+    
+    let largest: Arc<Mutex<LargestFiles>> = Arc::new(Mutex::new(BinaryHeap::with_capacity(limit + 1)));
+    
+    let walker = WalkBuilder::new(&root)
+        .hidden(false)
+        .git_ignore(false)
+        .threads(4)
+        .build_parallel();
+    
+    let largest_clone = largest.clone();
+    walker.run(move || {
+        let largest = largest_clone.clone();
+        Box::new(move |entry_result| {
+            let Ok(entry) = entry_result else {
+                return ignore::WalkState::Continue;
+            };
+            if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                return ignore::WalkState::Continue;
+            }
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if size < min_size_bytes {
+                return ignore::WalkState::Continue;
+            }
+            
+            if let Ok(mut heap) = largest.lock() {
+                let beats_smallest = heap.peek().is_some_and(|Reverse((smallest, _))| size > *smallest);
+                if heap.len() < limit || beats_smallest {
+                    heap.push(Reverse((size, entry.path().to_string_lossy().to_string())));
+                    if heap.len() > limit {
+                        heap.pop();
+                    }
+                }
+            }
+            ignore::WalkState::Continue
+        })
+    });
+    
+    let heap = std::mem::take(&mut *largest.lock().unwrap());
+    let mut results: Vec<SearchResult> = heap
+        .into_iter()
+        .filter_map(|Reverse((_, path))| stat_result(Path::new(&path)))
+        .collect();
+    results.sort_by_key(|r| Reverse(r.file_size));
+    results
+}
+
+// ============== FILE OPERATIONS ==============
+
+/// Result type for file operations
+#[derive(uniffi::Record, Clone)]
+pub struct FileOpResult {
+    pub success: bool,
+    pub message: String,
+    pub affected_count: i32,
+}
+
+/// Why a file operation failed, for callers that need to react to specific cases
+#[derive(uniffi::Error, Debug, Clone)]
+pub enum FileOpError {
+    NotFound,
+    PermissionDenied,
+    AlreadyExists,
+    InvalidPath,
+    IoError(String),
+    Other(String),
+}
+
+impl std::fmt::Display for FileOpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileOpError::NotFound => write!(f, "No such file or folder"),
+            FileOpError::PermissionDenied => write!(f, "Permission denied"),
+            FileOpError::AlreadyExists => write!(f, "Already exists"),
+            FileOpError::InvalidPath => write!(f, "Invalid path"),
+            FileOpError::IoError(message) | FileOpError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for FileOpError {}
+
+impl From<io::Error> for FileOpError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => FileOpError::NotFound,
+            io::ErrorKind::PermissionDenied => FileOpError::PermissionDenied,
+            io::ErrorKind::AlreadyExists => FileOpError::AlreadyExists,
+            io::ErrorKind::InvalidInput => FileOpError::InvalidPath,
+            _ => FileOpError::IoError(e.to_string()),
+        }
+    }
+}
+
+/// Outcome of a typed file operation; surfaces as a throwing function in Swift
+pub type FileOpResultTyped<T> = Result<T, FileOpError>;
+
+/// Like `create_folder`, but failures are reported as a `FileOpError`
+#[uniffi::export]
+pub fn create_folder_typed(path: String) -> FileOpResultTyped<()> {
+    if path.trim().is_empty() {
+        return Err(FileOpError::InvalidPath);
+    }
+    fs::create_dir_all(&path)?;
+    Ok(())
+}
+
+/// Like `rename_file`, but failures are reported as a `FileOpError`.
+/// Returns the new path.
+#[uniffi::export]
+pub fn rename_file_typed(path: String, new_name: String) -> FileOpResultTyped<String> {
+    if new_name.is_empty() || new_name.contains('/') {
+        return Err(FileOpError::InvalidPath);
+    }
+    let src_path = Path::new(&path);
+    let parent = src_path.parent().ok_or(FileOpError::InvalidPath)?;
+    
+    fs::symlink_metadata(src_path)?;
+    let new_path = parent.join(&new_name);
+    if fs::symlink_metadata(&new_path).is_ok() {
+        return Err(FileOpError::AlreadyExists);
+    }
+    
+    fs::rename(src_path, &new_path)?;
+    Ok(new_path.to_string_lossy().to_string())
+}
+
+/// Move files to a destination folder
+#[uniffi::export]
+pub fn move_files(source_paths: Vec<String>, destination: String) -> FileOpResult {
+    let dest_path = std::path::Path::new(&destination);
+    
+    // Create destination if it doesn't exist
+    if !dest_path.exists() {
+        if let Err(e) = fs::create_dir_all(dest_path) {
+            return FileOpResult {
+                success: false,
+                message: format!("Failed to create destination: {}", e),
+                affected_count: 0,
+            };
+        }
+    }
+    
+    let mut moved = 0;
+    let mut errors = Vec::new();
+    
+    for src in &source_paths {
+        let src_path = std::path::Path::new(src);
+        if let Some(file_name) = src_path.file_name() {
+            let dest_file = dest_path.join(file_name);
+            match fs::rename(src_path, &dest_file) {
+                Ok(_) => moved += 1,
+                Err(_e) => {
+                    // If rename fails (cross-device), try copy + delete
+                    if let Err(copy_err) = fs::copy(src_path, &dest_file) {
+                        errors.push(format!("{}: {}", src, copy_err));
+                    } else {
+                        let _ = fs::remove_file(src_path);
+                        moved += 1;
+                    }
+                }
+            }
+        }
+    }
+    
+    FileOpResult {
+        success: errors.is_empty(),
+        message: if errors.is_empty() {
+            format!("Moved {} files", moved)
+        } else {
+            format!("Moved {} files, {} errors: {}", moved, errors.len(), errors.join("; "))
+        },
+        affected_count: moved,
+    }
+}
+
+/// Copy files to a destination folder
+#[uniffi::export]
+pub fn copy_files(source_paths: Vec<String>, destination: String) -> FileOpResult {
+    let dest_path = std::path::Path::new(&destination);
+    
+    if !dest_path.exists() {
+        if let Err(e) = fs::create_dir_all(dest_path) {
+            return FileOpResult {
+                success: false,
+                message: format!("Failed to create destination: {}", e),
+                affected_count: 0,
+            };
+        }
+    }
+    
+    let mut copied = 0;
+    let mut errors = Vec::new();
+    
+    for src in &source_paths {
+        let src_path = std::path::Path::new(src);
+        if let Some(file_name) = src_path.file_name() {
+            let dest_file = dest_path.join(file_name);
+            match fs::copy(src_path, &dest_file) {
+                Ok(_) => copied += 1,
+                Err(e) => errors.push(format!("{}: {}", src, e)),
+            }
+        }
+    }
+    
+    FileOpResult {
+        success: errors.is_empty(),
+        message: if errors.is_empty() {
+            format!("Copied {} files", copied)
+        } else {
+            format!("Copied {} files, {} errors", copied, errors.len())
+        },
+        affected_count: copied,
+    }
+}
+
+/// What to do when a file with the same name already exists at the destination
+#[derive(uniffi::Enum, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Replace the existing item. An existing folder is deleted along with
+    /// everything in it, not merged with the incoming item. A source that is
+    /// already at the destination is reported as an error and left untouched.
+    Overwrite,
+    /// Leave both the source and the existing file untouched
+    Skip,
+    /// Keep both, giving the incoming file a numbered name ("report 1.pdf")
+    Rename,
+    /// Abort the whole batch before anything is changed
+    Error,
+}
+
+// Destination for `src` inside `dest_dir` under `strategy`, or None to skip it.
+// `Error` conflicts are checked up front by `find_conflicts`, so they're treated as Overwrite here.
+fn resolve_conflict(src: &Path, dest_dir: &Path, strategy: ConflictStrategy) -> io::Result<Option<PathBuf>> {
+    let file_name = src.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "No file name"))?;
+    let dest_file = dest_dir.join(file_name);
+    if fs::symlink_metadata(&dest_file).is_err() {
+        return Ok(Some(dest_file));
+    }
+    
+    match strategy {
+        ConflictStrategy::Skip => Ok(None),
+        ConflictStrategy::Rename => {
+            let name = unique_name_in(src, |candidate| dest_dir.join(candidate).exists());
+            Ok(Some(dest_dir.join(name)))
+        }
+        ConflictStrategy::Overwrite | ConflictStrategy::Error => {
+            // The source is already in `dest_dir`: replacing it would delete or truncate it
+            if same_item(src, &dest_file) {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Source and destination are the same item"));
+            }
+            // Replacing a folder discards its contents; see `ConflictStrategy::Overwrite`
+            if dest_file.is_dir() {
+                fs::remove_dir_all(&dest_file)?;
+            }
+            Ok(Some(dest_file))
+        }
+    }
+}
+
+fn same_item(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// Sources whose name is already taken in `dest_dir`
+fn find_conflicts(source_paths: &[String], dest_dir: &Path) -> Vec<String> {
+    source_paths
+        .iter()
+        .filter(|src| {
+            Path::new(src)
+                .file_name()
+                .is_some_and(|name| fs::symlink_metadata(dest_dir.join(name)).is_ok())
+        })
+        .cloned()
+        .collect()
+}
+
+// Rename, falling back to copy + delete across devices
+fn move_entry(src_path: &Path, dest_file: &Path) -> io::Result<()> {
+    if fs::rename(src_path, dest_file).is_ok() {
+        return Ok(());
+    }
+    fs::copy(src_path, dest_file)?;
+    fs::remove_file(src_path)
+}
+
+/// Move files to a destination folder, resolving name clashes with `strategy`
+#[uniffi::export]
+pub fn move_files_with_strategy(source_paths: Vec<String>, destination: String, strategy: ConflictStrategy) -> FileOpResult {
+    transfer_with_strategy(&source_paths, &destination, strategy, "Moved", move_entry)
+}
+
+/// Copy files to a destination folder, resolving name clashes with `strategy`
+#[uniffi::export]
+pub fn copy_files_with_strategy(source_paths: Vec<String>, destination: String, strategy: ConflictStrategy) -> FileOpResult {
+    transfer_with_strategy(&source_paths, &destination, strategy, "Copied", |src, dest| {
+        fs::copy(src, dest).map(|_| ())
+    })
+}
+
+fn transfer_with_strategy(
+    source_paths: &[String],
+    destination: &str,
+    strategy: ConflictStrategy,
+    verb: &str,
+    transfer: impl Fn(&Path, &Path) -> io::Result<()>,
+) -> FileOpResult {
+    let dest_path = Path::new(destination);
+    
+    if !dest_path.exists() {
+        if let Err(e) = fs::create_dir_all(dest_path) {
+            return FileOpResult {
+                success: false,
+                message: format!("Failed to create destination: {}", e),
+                affected_count: 0,
+            };
+        }
+    }
+    
+    if strategy == ConflictStrategy::Error {
+        let conflicts = find_conflicts(source_paths, dest_path);
+        if !conflicts.is_empty() {
+            return FileOpResult {
+                success: false,
+                message: format!("Already exists at destination: {}", conflicts.join("; ")),
+                affected_count: 0,
+            };
+        }
+    }
+    
+    let mut transferred = 0;
+    let mut skipped = 0;
+    let mut errors = Vec::new();
+    
+    for src in source_paths {
+        let src_path = Path::new(src);
+        let outcome = resolve_conflict(src_path, dest_path, strategy).and_then(|dest_file| match dest_file {
+            Some(dest_file) => transfer(src_path, &dest_file).map(|_| true),
+            None => Ok(false),
+        });
+        match outcome {
+            Ok(true) => transferred += 1,
+            Ok(false) => skipped += 1,
+            Err(e) => errors.push(format!("{}: {}", src, e)),
+        }
+    }
+    
+    let mut message = format!("{} {} files", verb, transferred);
+    if skipped > 0 {
+        message.push_str(&format!(", skipped {} existing", skipped));
+    }
+    if !errors.is_empty() {
+        message.push_str(&format!(", {} errors: {}", errors.len(), errors.join("; ")));
+    }
+    
+    FileOpResult {
+        success: errors.is_empty(),
+        message,
+        affected_count: transferred,
+    }
+}
+
+/// Copy files to a destination folder at no more than `max_bytes_per_second`
+/// (0 means unlimited), so background transfers don't saturate the disk
+#[uniffi::export]
+pub fn copy_files_throttled(source_paths: Vec<String>, destination: String, max_bytes_per_second: u64) -> FileOpResult {
+    let dest_path = Path::new(&destination);
+    
+    if !dest_path.exists() {
+        if let Err(e) = fs::create_dir_all(dest_path) {
+            return FileOpResult {
+                success: false,
+                message: format!("Failed to create destination: {}", e),
+                affected_count: 0,
+            };
+        }
+    }
+    
+    let mut throttle = Throttle { max_bytes_per_second, started: Instant::now(), bytes: 0 };
+    let mut copied = 0;
+    let mut errors = Vec::new();
+    
+    for src in &source_paths {
+        let src_path = Path::new(src);
+        if let Some(file_name) = src_path.file_name() {
+            match copy_throttled(src_path, &dest_path.join(file_name), &mut throttle) {
+                Ok(_) => copied += 1,
+                Err(e) => errors.push(format!("{}: {}", src, e)),
+            }
+        }
+    }
+    
+    FileOpResult {
+        success: errors.is_empty(),
+        message: if errors.is_empty() {
+            format!("Copied {} files", copied)
+        } else {
+            format!("Copied {} files, {} errors: {}", copied, errors.len(), errors.join("; "))
+        },
+        affected_count: copied,
+    }
+}
+
+// Running rate limit shared by all files of one `copy_files_throttled` call
+struct Throttle {
+    max_bytes_per_second: u64,
+    started: Instant,
+    bytes: u64,
+}
+
+impl Throttle {
+    // Account for `n` more bytes, sleeping until the average rate is back under the limit
+    fn consume(&mut self, n: u64) {
+        self.bytes += n;
+        if self.max_bytes_per_second == 0 {
+            return;
+        }
+        let expected = std::time::Duration::from_secs_f64(self.bytes as f64 / self.max_bytes_per_second as f64);
+        if let Some(ahead) = expected.checked_sub(self.started.elapsed()) {
+            std::thread::sleep(ahead);
+        }
+    }
+}
+
+fn copy_throttled(src: &Path, dest: &Path, throttle: &mut Throttle) -> io::Result<()> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+    
+    let mut reader = fs::File::open(src)?;
+    let mut writer = BufWriter::new(fs::File::create(dest)?);
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    
+    loop {
+        let n = read_full(&mut reader, &mut buf)?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buf[..n])?;
+        throttle.consume(n as u64);
+    }
+    writer.flush()?;
+    
+    fs::set_permissions(dest, reader.metadata()?.permissions())
+}
+
+/// Copy files to a destination folder, keeping permissions, timestamps and
+/// extended attributes (Finder tags, quarantine flags, ...)
+#[uniffi::export]
+pub fn copy_files_preserving_attrs(source_paths: Vec<String>, destination: String) -> FileOpResult {
+    let dest_path = std::path::Path::new(&destination);
+    
+    if !dest_path.exists() {
+        if let Err(e) = fs::create_dir_all(dest_path) {
+            return FileOpResult {
+                success: false,
+                message: format!("Failed to create destination: {}", e),
+                affected_count: 0,
+            };
+        }
+    }
+    
+    let mut copied = 0;
+    let mut errors = Vec::new();
+    
+    for src in &source_paths {
+        let src_path = std::path::Path::new(src);
+        if let Some(file_name) = src_path.file_name() {
+            let dest_file = dest_path.join(file_name);
+            match fs::copy(src_path, &dest_file) {
+                Ok(_) => {
+                    copied += 1;
+                    if let Err(e) = copy_attributes(src_path, &dest_file) {
+                        errors.push(format!("{}: attributes not preserved: {}", src, e));
+                    }
+                }
+                Err(e) => errors.push(format!("{}: {}", src, e)),
+            }
+        }
+    }
+    
+    FileOpResult {
+        success: errors.is_empty(),
+        message: if errors.is_empty() {
+            format!("Copied {} files", copied)
+        } else {
+            format!("Copied {} files, {} errors: {}", copied, errors.len(), errors.join("; "))
+        },
+        affected_count: copied,
+    }
+}
+
+// Copy xattrs, then timestamps, then permissions (last, in case they make the file read-only)
+fn copy_attributes(src: &Path, dest: &Path) -> io::Result<()> {
+    if xattr::SUPPORTED_PLATFORM {
+        for name in xattr::list(src)? {
+            if let Some(value) = xattr::get(src, &name)? {
+                xattr::set(dest, &name, &value)?;
+            }
+        }
+    }
+    
+    let metadata = fs::metadata(src)?;
+    filetime::set_file_times(
+        dest,
+        filetime::FileTime::from_last_access_time(&metadata),
+        filetime::FileTime::from_last_modification_time(&metadata),
+    )?;
+    fs::set_permissions(dest, metadata.permissions())
+}
+
+/// Move files to Trash. On macOS this is `~/.Trash`; on Linux it follows the
+/// freedesktop Trash spec so the desktop's trash can restore items.
+#[uniffi::export]
+pub fn trash_files(paths: Vec<String>) -> FileOpResult {
+    let mut trashed = 0;
+    let mut errors = Vec::new();
+    
+    for src in &paths {
+        let src_path = std::path::Path::new(src);
+        if src_path.file_name().is_some() {
+            match trash_one(src_path) {
+                Ok(_) => trashed += 1,
+                Err(e) => errors.push(format!("{}: {}", src, e)),
+            }
+        }
+    }
+    
+    FileOpResult {
+        success: errors.is_empty(),
+        message: if errors.is_empty() {
+            format!("Moved {} items to Trash", trashed)
+        } else {
+            format!("Trashed {} items, {} errors", trashed, errors.len())
+        },
+        affected_count: trashed,
+    }
+}
+
+// First "name", "name 1", "name 2", ... (keeping the extension) for which `taken` is false
+fn unique_name_in(src_path: &Path, taken: impl Fn(&str) -> bool) -> String {
+    let file_name = src_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let stem = src_path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = src_path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    
+    let mut name = file_name;
+    let mut counter = 1;
+    while taken(name.as_str()) {
+        name = format!("{} {}{}", stem, counter, ext);
+        counter += 1;
+    }
+    name
+}
+
+#[cfg(not(target_os = "linux"))]
+fn trash_one(src_path: &Path) -> io::Result<()> {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let trash_path = Path::new(&home).join(".Trash");
+    
+    // Generate unique name if file already exists in trash
+    let name = unique_name_in(src_path, |name| trash_path.join(name).exists());
+    fs::rename(src_path, trash_path.join(name))
+}
+
+// freedesktop Trash: the item goes to `files/` and a `.trashinfo` in `info/`
+// records its original path and deletion date
+#[cfg(target_os = "linux")]
+fn trash_one(src_path: &Path) -> io::Result<()> {
+    let data_home = env::var("XDG_DATA_HOME").unwrap_or_else(|_| {
+        let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        format!("{}/.local/share", home)
+    });
+    let trash = Path::new(&data_home).join("Trash");
+    let files_dir = trash.join("files");
+    let info_dir = trash.join("info");
+    fs::create_dir_all(&files_dir)?;
+    fs::create_dir_all(&info_dir)?;
+    
+    let original = std::path::absolute(src_path)?;
+    let name = unique_name_in(src_path, |name| {
+        files_dir.join(name).exists() || info_dir.join(format!("{}.trashinfo", name)).exists()
+    });
+    
+    // Claim the name by creating the info file first, as the spec requires
+    let info_path = info_dir.join(format!("{}.trashinfo", name));
+    let mut info = fs::OpenOptions::new().write(true).create_new(true).open(&info_path)?;
+    let encoded = percent_encoding::utf8_percent_encode(&original.to_string_lossy(), URL_PATH_ENCODE_SET).to_string();
+    write!(info, "[Trash Info]\nPath={}\nDeletionDate={}\n", encoded, local_timestamp())?;
+    
+    if let Err(e) = fs::rename(src_path, files_dir.join(&name)) {
+        let _ = fs::remove_file(&info_path);
+        return Err(e);
+    }
+    Ok(())
+}
+
+// Local time as YYYY-MM-DDThh:mm:ss, the format `.trashinfo` files use
+#[cfg(target_os = "linux")]
+fn local_timestamp() -> String {
+    let now = unsafe { libc::time(std::ptr::null_mut()) };
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    unsafe { libc::localtime_r(&now, &mut tm) };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min,
+        tm.tm_sec
+    )
+}
+
+/// Securely delete a file by overwriting its contents before removal.
+/// Each pass writes random bytes, followed by a final pass of zeros.
+/// This is best-effort only: on SSDs with wear-levelling (and on copy-on-write
+/// filesystems like APFS) old blocks may survive the overwrite.
+/// Symlinks are refused rather than followed, so a link's target is never overwritten.
+#[uniffi::export]
+pub fn secure_delete_file(path: String, passes: u32) -> FileOpResult {
+    use rand::RngCore;
+    
+    if !(1..=7).contains(&passes) {
+        return FileOpResult {
+            success: false,
+            message: format!("Passes must be between 1 and 7, got {}", passes),
+            affected_count: 0,
+        };
+    }
+    
+    let src_path = std::path::Path::new(&path);
+    if !fs::symlink_metadata(src_path).is_ok_and(|m| m.file_type().is_file()) {
+        return FileOpResult {
+            success: false,
+            message: format!("Not a regular file: {}", path),
+            affected_count: 0,
+        };
+    }
+    
+    let mut options = fs::OpenOptions::new();
+    options.write(true);
+    // Also refuse a symlink swapped in after the check above
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::custom_flags(&mut options, libc::O_NOFOLLOW);
+    let mut file = match options.open(src_path) {
+        Ok(f) => f,
+        Err(e) => return FileOpResult {
+            success: false,
+            message: format!("Failed to open file: {}", e),
+            affected_count: 0,
+        },
+    };
+    
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut rng = rand::thread_rng();
+    let mut buffer = vec![0u8; 64 * 1024];
+    
+    // `passes` random overwrites, then one pass of zeros
+    for pass in 0..=passes {
+        let random = pass < passes;
+        if file.seek(SeekFrom::Start(0)).is_err() {
+            return FileOpResult {
+                success: false,
+                message: "Failed to seek file".to_string(),
+                affected_count: 0,
+            };
+        }
+        
+        let mut remaining = len;
+        while remaining > 0 {
+            let chunk = remaining.min(buffer.len() as u64) as usize;
+            if random {
+                rng.fill_bytes(&mut buffer[..chunk]);
+            } else {
+                buffer[..chunk].fill(0);
+            }
+            if let Err(e) = file.write_all(&buffer[..chunk]) {
+                return FileOpResult {
+                    success: false,
+                    message: format!("Overwrite failed: {}", e),
+                    affected_count: 0,
+                };
+            }
+            remaining -= chunk as u64;
+        }
+        
+        if let Err(e) = file.sync_all() {
+            return FileOpResult {
+                success: false,
+                message: format!("Sync failed: {}", e),
+                affected_count: 0,
+            };
+        }
+    }
+    drop(file);
+    
+    match fs::remove_file(src_path) {
+        Ok(_) => FileOpResult {
+            success: true,
+            message: format!("Securely deleted with {} passes", passes),
+            affected_count: 1,
+        },
+        Err(e) => FileOpResult {
+            success: false,
+            message: format!("Failed to remove file: {}", e),
+            affected_count: 0,
+        },
+    }
+}
+
+/// Rename a file
+#[uniffi::export]
+pub fn rename_file(path: String, new_name: String) -> FileOpResult {
+    let src_path = std::path::Path::new(&path);
+    
+    if let Some(parent) = src_path.parent() {
+        let new_path = parent.join(&new_name);
+        
+        if new_path.exists() {
+            return FileOpResult {
+                success: false,
+                message: format!("File '{}' already exists", new_name),
+                affected_count: 0,
+            };
+        }
+        
+        match fs::rename(src_path, &new_path) {
+            Ok(_) => FileOpResult {
+                success: true,
+                message: format!("Renamed to '{}'", new_name),
+                affected_count: 1,
+            },
+            Err(e) => FileOpResult {
+                success: false,
+                message: format!("Rename failed: {}", e),
+                affected_count: 0,
+            },
+        }
+    } else {
+        FileOpResult {
+            success: false,
+            message: "Invalid path".to_string(),
+            affected_count: 0,
+        }
+    }
+}
+
+/// Rename a file to `new_stem` plus its current extension, so "quarterly_report"
+/// renames `draft.pdf` to `quarterly_report.pdf`. Folders and extensionless files get `new_stem` as is.
+#[uniffi::export]
+pub fn rename_file_keep_extension(path: String, new_stem: String) -> FileOpResult {
+    if new_stem.trim().is_empty() || new_stem.contains(['/', '\\']) {
+        return FileOpResult {
+            success: false,
+            message: format!("Invalid name '{}': must not be empty or contain path separators", new_stem),
+            affected_count: 0,
+        };
+    }
+    
+    let src_path = Path::new(&path);
+    let new_name = match src_path.extension() {
+        Some(ext) if !src_path.is_dir() => format!("{}.{}", new_stem, ext.to_string_lossy()),
+        _ => new_stem,
+    };
+    rename_file(path, new_name)
+}
+
+/// Create a new folder
+#[uniffi::export]
+pub fn create_folder(path: String) -> FileOpResult {
+    match fs::create_dir_all(&path) {
+        Ok(_) => FileOpResult {
+            success: true,
+            message: "Created folder".to_string(),
+            affected_count: 1,
+        },
+        Err(e) => FileOpResult {
+            success: false,
+            message: format!("Failed to create folder: {}", e),
+            affected_count: 0,
+        },
+    }
+}
+
+/// Create several folders (and any missing parents) in one call
+#[uniffi::export]
+pub fn batch_create_folders(paths: Vec<String>) -> FileOpResult {
+    let mut created = 0;
+    let mut errors = Vec::new();
+    
+    for path in &paths {
+        match fs::create_dir_all(path) {
+            Ok(_) => created += 1,
+            Err(e) => errors.push(format!("{}: {}", path, e)),
+        }
+    }
+    
+    FileOpResult {
+        success: errors.is_empty(),
+        message: if errors.is_empty() {
+            format!("Created {} folders", created)
+        } else {
+            format!("Created {} folders, {} errors: {}", created, errors.len(), errors.join("; "))
+        },
+        affected_count: created,
+    }
+}
+
+/// Create a folder tree under `base` from relative paths such as "src/models"
+/// or "docs". Absolute paths and `..` components are rejected.
+#[uniffi::export]
+pub fn create_project_structure(base: String, structure: Vec<String>) -> FileOpResult {
+    use std::path::Component;
+    
+    let base_path = Path::new(&base);
+    let mut paths = Vec::new();
+    for relative in &structure {
+        let relative_path = Path::new(relative);
+        if !relative_path.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+            return FileOpResult {
+                success: false,
+                message: format!("Not a relative path inside the project: {}", relative),
+                affected_count: 0,
+            };
+        }
+        paths.push(base_path.join(relative_path).to_string_lossy().to_string());
+    }
+    
+    batch_create_folders(paths)
+}
+
+/// Create a Finder alias at `alias_path` pointing to `target`. Unlike a symlink,
+/// an alias keeps working after the target is moved. Elsewhere this creates a symlink.
+#[uniffi::export]
+pub fn create_alias(target: String, alias_path: String) -> FileOpResult {
+    let target_path = Path::new(&target);
+    let alias = Path::new(&alias_path);
+    
+    if !target_path.exists() {
+        return FileOpResult {
+            success: false,
+            message: format!("Target does not exist: {}", target),
+            affected_count: 0,
+        };
+    }
+    if fs::symlink_metadata(alias).is_ok() {
+        return FileOpResult {
+            success: false,
+            message: format!("Already exists: {}", alias_path),
+            affected_count: 0,
+        };
+    }
+    
+    match make_alias(target_path, alias) {
+        Ok(_) => FileOpResult {
+            success: true,
+            message: format!("Created alias to {}", target),
+            affected_count: 1,
+        },
+        Err(e) => FileOpResult {
+            success: false,
+            message: format!("Failed to create alias: {}", e),
+            affected_count: 0,
+        },
+    }
+}
+
+// Finder makes the alias in a folder under a name of its choosing, so rename it afterwards.
+// Paths are passed as arguments to avoid quoting them inside the script.
+#[cfg(target_os = "macos")]
+fn make_alias(target: &Path, alias: &Path) -> io::Result<()> {
+    let parent = alias.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let name = alias
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Alias path has no file name"))?;
+    
+    let output = std::process::Command::new("osascript")
+        .args([
+            "-e", "on run argv",
+            "-e", "tell application \"Finder\"",
+            "-e", "set newAlias to make new alias file at (POSIX file (item 2 of argv) as alias) to (POSIX file (item 1 of argv) as alias)",
+            "-e", "set name of newAlias to (item 3 of argv)",
+            "-e", "end tell",
+            "-e", "end run",
+        ])
+        .arg(target)
+        .arg(fs::canonicalize(parent)?)
+        .arg(name)
+        .output()?;
+    
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn make_alias(target: &Path, alias: &Path) -> io::Result<()> {
+    create_symlink(target, alias)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn create_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    if target.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+// Finder marks alias files with the kIsAlias bit in the fdFlags word of their Finder info
+#[cfg(target_os = "macos")]
+fn is_finder_alias(path: &Path) -> bool {
+    const K_IS_ALIAS: u16 = 0x8000;
+    match xattr::get(path, "com.apple.FinderInfo") {
+        Ok(Some(info)) if info.len() >= 10 => u16::from_be_bytes([info[8], info[9]]) & K_IS_ALIAS != 0,
+        _ => false,
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn is_finder_alias(_path: &Path) -> bool {
+    false
+}
+
+/// Create a hard link at `link_path` to the file `target`. Hard links only work for
+/// files (not folders) and both paths must be on the same volume.
+#[uniffi::export]
+pub fn create_hardlink(target: String, link_path: String) -> FileOpResult {
+    let target_path = Path::new(&target);
+    let link = Path::new(&link_path);
+    
+    let error = |message: String| FileOpResult {
+        success: false,
+        message,
+        affected_count: 0,
+    };
+    
+    match fs::metadata(target_path) {
+        Err(_) => return error(format!("Target does not exist: {}", target)),
+        Ok(meta) if meta.is_dir() => {
+            return error(format!("Cannot hard link a folder: {}. Use an alias or symlink instead.", target));
+        }
+        Ok(_) => {}
+    }
+    if fs::symlink_metadata(link).is_ok() {
+        return error(format!("Already exists: {}", link_path));
+    }
+    
+    match fs::hard_link(target_path, link) {
+        Ok(_) => FileOpResult {
+            success: true,
+            message: format!("Created hard link to {}", target),
+            affected_count: 1,
+        },
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => error(format!(
+            "Cannot hard link across volumes: {} and {} are on different file systems",
+            target, link_path
+        )),
+        Err(e) => error(format!("Failed to create hard link: {}", e)),
+    }
+}
+
+/// Compress files into a ZIP archive
+#[uniffi::export]
+pub fn compress_files(paths: Vec<String>, archive_path: String) -> FileOpResult {
+    let file = match fs::File::create(&archive_path) {
+        Ok(f) => f,
+        Err(e) => return FileOpResult {
+            success: false,
+            message: format!("Failed to create archive: {}", e),
+            affected_count: 0,
+        },
+    };
+    
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    
+    let added = add_files_to_zip(&mut zip, &paths, |_| options, |_, _| {});
+    
+    if zip.finish().is_err() {
+        return FileOpResult {
+            success: false,
+            message: "Failed to finalize archive".to_string(),
+            affected_count: 0,
+        };
+    }
+    
+    FileOpResult {
+        success: true,
+        message: format!("Compressed {} files", added),
+        affected_count: added,
+    }
+}
+
+/// Compress files into a 7-Zip archive (LZMA2). Slower than `compress_files` but
+/// noticeably smaller for documents, which suits large backup archives.
+#[uniffi::export]
+pub fn compress_files_7z(paths: Vec<String>, archive_path: String) -> FileOpResult {
+    let mut writer = match sevenz_rust::SevenZWriter::create(&archive_path) {
+        Ok(writer) => writer,
+        Err(e) => return FileOpResult {
+            success: false,
+            message: format!("Failed to create archive: {}", e),
+            affected_count: 0,
+        },
+    };
+    
+    let mut added = 0;
+    for src in &paths {
+        let src_path = Path::new(src);
+        let Some(file_name) = src_path.file_name() else {
+            continue;
+        };
+        if !src_path.is_file() {
+            continue;
+        }
+        if let Ok(f) = fs::File::open(src_path) {
+            let entry = sevenz_rust::SevenZArchiveEntry::from_path(src_path, file_name.to_string_lossy().to_string());
+            if writer.push_archive_entry(entry, Some(f)).is_ok() {
+                added += 1;
+            }
+        }
+    }
+    
+    if writer.finish().is_err() {
+        return FileOpResult {
+            success: false,
+            message: "Failed to finalize archive".to_string(),
+            affected_count: 0,
+        };
+    }
+    
+    FileOpResult {
+        success: true,
+        message: format!("Compressed {} files", added),
+        affected_count: added,
+    }
+}
+
+/// Extract a 7-Zip archive into `destination`, creating it if needed. Entries whose
+/// names would land outside `destination` (absolute or containing `..`) are skipped.
+#[uniffi::export]
+pub fn extract_7z_archive(archive_path: String, destination: String) -> FileOpResult {
+    let file = match fs::File::open(&archive_path) {
+        Ok(f) => f,
+        Err(e) => return FileOpResult {
+            success: false,
+            message: format!("Failed to open archive: {}", e),
+            affected_count: 0,
+        },
+    };
+    if let Err(e) = fs::create_dir_all(&destination) {
+        return FileOpResult {
+            success: false,
+            message: format!("Failed to create destination: {}", e),
+            affected_count: 0,
+        };
+    }
+    
+    let mut extracted = 0;
+    let mut skipped = 0;
+    let result = sevenz_rust::decompress_with_extract_fn(BufReader::new(file), &destination, |entry, reader, dest| {
+        let name = Path::new(entry.name());
+        let escapes = name.is_absolute()
+            || name.components().any(|c| matches!(c, std::path::Component::ParentDir | std::path::Component::Prefix(_)));
+        if escapes {
+            skipped += 1;
+            return Ok(true);
+        }
+        let keep_going = sevenz_rust::default_entry_extract_fn(entry, reader, dest)?;
+        if !entry.is_directory() {
+            extracted += 1;
+        }
+        Ok(keep_going)
+    });
+    
+    match result {
+        Ok(_) => FileOpResult {
+            success: true,
+            message: if skipped == 0 {
+                format!("Extracted {} files", extracted)
+            } else {
+                format!("Extracted {} files, skipped {} with unsafe paths", extracted, skipped)
+            },
+            affected_count: extracted,
+        },
+        Err(e) => FileOpResult {
+            success: false,
+            message: format!("Extraction failed after {} files: {}", extracted, e),
+            affected_count: extracted,
+        },
+    }
+}
+
+// Kinds whose formats are already compressed, so deflating them only costs CPU
+const PRECOMPRESSED_KINDS: &[&str] = &[
+    "JPEG Image", "PNG Image", "GIF Image", "HEIC Image",
+    "MP4 Video", "QuickTime Movie", "MP3 Audio",
+    "ZIP Archive", "Disk Image",
+];
+
+/// Compress files into a ZIP archive, storing already-compressed formats
+/// (images, videos, archives) as-is and deflating everything else
+#[uniffi::export]
+pub fn compress_files_smart(paths: Vec<String>, archive_path: String) -> FileOpResult {
+    let file = match fs::File::create(&archive_path) {
+        Ok(f) => f,
+        Err(e) => return FileOpResult {
+            success: false,
+            message: format!("Failed to create archive: {}", e),
+            affected_count: 0,
+        },
+    };
+    
+    let mut zip = zip::ZipWriter::new(file);
+    let added = add_files_to_zip(&mut zip, &paths, |path| {
+        let method = if PRECOMPRESSED_KINDS.contains(&get_file_kind(path, false).as_str()) {
+            zip::CompressionMethod::Stored
+        } else {
+            zip::CompressionMethod::Deflated
+        };
+        zip::write::SimpleFileOptions::default().compression_method(method)
+    }, |_, _| {});
+    
+    if zip.finish().is_err() {
+        return FileOpResult {
+            success: false,
+            message: "Failed to finalize archive".to_string(),
+            affected_count: 0,
+        };
+    }
+    
+    FileOpResult {
+        success: true,
+        message: format!("Compressed {} files", added),
+        affected_count: added,
+    }
+}
+
+// Add each regular file to the archive under its file name, with the options
+// `options_for` picks for it, returning how many were added. `on_path_done` is
+// called after each input path with its index and entry name, if it was added.
+fn add_files_to_zip<W: Write + Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    paths: &[String],
+    options_for: impl Fn(&Path) -> zip::write::SimpleFileOptions,
+    mut on_path_done: impl FnMut(usize, Option<&str>),
+) -> i32 {
+    let mut added = 0;
+    
+    for (i, src) in paths.iter().enumerate() {
         let src_path = std::path::Path::new(src);
+        let mut entry_name = None;
         if let Some(file_name) = src_path.file_name() {
-            let dest_file = dest_path.join(file_name);
-            match fs::rename(src_path, &dest_file) {
-                Ok(_) => moved += 1,
-                Err(_e) => {
-                    // If rename fails (cross-device), try copy + delete
-                    if let Err(copy_err) = fs::copy(src_path, &dest_file) {
-                        errors.push(format!("{}: {}", src, copy_err));
-                    } else {
-                        let _ = fs::remove_file(src_path);
-                        moved += 1;
+            if src_path.is_file() {
+                if let Ok(mut f) = fs::File::open(src_path) {
+                    // Entries of 4 GB or more need ZIP64 headers
+                    let large = f.metadata().map(|m| m.len() >= u32::MAX as u64).unwrap_or(false);
+                    let name = file_name.to_string_lossy().to_string();
+                    if zip.start_file(name.as_str(), options_for(src_path).large_file(large)).is_ok()
+                        && io::copy(&mut f, zip).is_ok()
+                    {
+                        added += 1;
+                        entry_name = Some(name);
                     }
                 }
             }
         }
+        on_path_done(i, entry_name.as_deref());
     }
     
-    FileOpResult {
-        success: errors.is_empty(),
-        message: if errors.is_empty() {
-            format!("Moved {} files", moved)
-        } else {
-            format!("Moved {} files, {} errors: {}", moved, errors.len(), errors.join("; "))
-        },
-        affected_count: moved,
-    }
+    added
 }
 
-/// Copy files to a destination folder
+/// Check a ZIP archive for corruption by decompressing every entry. The zip
+/// reader compares each entry's CRC-32 with the one in the central directory
+/// once the entry has been read to the end.
 #[uniffi::export]
-pub fn copy_files(source_paths: Vec<String>, destination: String) -> FileOpResult {
-    let dest_path = std::path::Path::new(&destination);
+pub fn verify_archive_integrity(archive_path: String) -> FileOpResult {
+    let file = match fs::File::open(&archive_path) {
+        Ok(f) => f,
+        Err(e) => return FileOpResult {
+            success: false,
+            message: format!("Failed to open archive: {}", e),
+            affected_count: 0,
+        },
+    };
     
-    if !dest_path.exists() {
-        if let Err(e) = fs::create_dir_all(dest_path) {
-            return FileOpResult {
-                success: false,
-                message: format!("Failed to create destination: {}", e),
-                affected_count: 0,
-            };
-        }
-    }
+    let mut archive = match zip::ZipArchive::new(BufReader::new(file)) {
+        Ok(archive) => archive,
+        Err(e) => return FileOpResult {
+            success: false,
+            message: format!("Not a readable ZIP archive: {}", e),
+            affected_count: 0,
+        },
+    };
     
-    let mut copied = 0;
-    let mut errors = Vec::new();
+    let mut verified = 0;
+    let mut failures = Vec::new();
     
-    for src in &source_paths {
-        let src_path = std::path::Path::new(src);
-        if let Some(file_name) = src_path.file_name() {
-            let dest_file = dest_path.join(file_name);
-            match fs::copy(src_path, &dest_file) {
-                Ok(_) => copied += 1,
-                Err(e) => errors.push(format!("{}: {}", src, e)),
+    for i in 0..archive.len() {
+        match archive.by_index(i) {
+            Ok(mut entry) => {
+                let name = entry.name().to_string();
+                match io::copy(&mut entry, &mut io::sink()) {
+                    Ok(_) => verified += 1,
+                    Err(e) => failures.push(format!("{}: {}", name, e)),
+                }
             }
+            Err(e) => failures.push(format!("entry {}: {}", i, e)),
         }
     }
     
     FileOpResult {
-        success: errors.is_empty(),
-        message: if errors.is_empty() {
-            format!("Copied {} files", copied)
+        success: failures.is_empty(),
+        message: if failures.is_empty() {
+            format!("All {} entries are intact", verified)
         } else {
-            format!("Copied {} files, {} errors", copied, errors.len())
+            format!("{} of {} entries are corrupt: {}", failures.len(), archive.len(), failures.join("; "))
         },
-        affected_count: copied,
+        affected_count: verified,
     }
 }
 
-/// Move files to Trash
+/// Zip `paths` and save the archive as `archive_name` on an SMB share given as
+/// `smb://[user@]server/share[/folder]`. The archive is built locally first. If the
+/// share isn't mounted it is mounted with `mount_smbfs` (macOS only) and unmounted
+/// afterwards. The password always comes from the keychain; one written in the URL
+/// is ignored, since command-line arguments are visible to other local users.
 #[uniffi::export]
-pub fn trash_files(paths: Vec<String>) -> FileOpResult {
-    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    let trash_path = std::path::Path::new(&home).join(".Trash");
+pub fn archive_to_network_location(paths: Vec<String>, smb_url: String, archive_name: String) -> FileOpResult {
+    let failure = |message: String| FileOpResult { success: false, message, affected_count: 0 };
     
-    let mut trashed = 0;
-    let mut errors = Vec::new();
+    let Some(share) = parse_smb_url(&smb_url) else {
+        return failure("Expected an smb://server/share URL".to_string());
+    };
+    if archive_name.is_empty() || archive_name.contains('/') {
+        return failure("Archive name must be a file name".to_string());
+    }
     
-    for src in &paths {
-        let src_path = std::path::Path::new(src);
-        if let Some(file_name) = src_path.file_name() {
-            // Generate unique name if file already exists in trash
-            let mut dest_file = trash_path.join(file_name);
-            let mut counter = 1;
-            while dest_file.exists() {
-                let stem = src_path.file_stem().unwrap_or_default().to_string_lossy();
-                let ext = src_path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
-                dest_file = trash_path.join(format!("{} {}{}", stem, counter, ext));
-                counter += 1;
-            }
-            
-            match fs::rename(src_path, &dest_file) {
-                Ok(_) => trashed += 1,
-                Err(e) => errors.push(format!("{}: {}", src, e)),
-            }
-        }
+    // A slow or flaky share shouldn't leave a half-written ZIP behind
+    let temp_archive = env::temp_dir().join(format!("fast-finder-{}-{}", std::process::id(), archive_name));
+    let zipped = compress_files(paths, temp_archive.to_string_lossy().to_string());
+    if !zipped.success {
+        let _ = fs::remove_file(&temp_archive);
+        return zipped;
     }
     
-    FileOpResult {
-        success: errors.is_empty(),
-        message: if errors.is_empty() {
-            format!("Moved {} items to Trash", trashed)
-        } else {
-            format!("Trashed {} items, {} errors", trashed, errors.len())
+    let (mount_point, freshly_mounted) = match find_smb_mount(&share) {
+        Some(mount_point) => (mount_point, false),
+        None => match mount_smb_share(&share) {
+            Ok(mount_point) => (mount_point, true),
+            Err(message) => {
+                let _ = fs::remove_file(&temp_archive);
+                return failure(message);
+            }
         },
-        affected_count: trashed,
+    };
+    
+    let dest_dir = mount_point.join(&share.folder);
+    let moved = fs::create_dir_all(&dest_dir).and_then(|_| move_entry(&temp_archive, &dest_dir.join(&archive_name)));
+    let unmount_note = match freshly_mounted.then(|| unmount_smb_share(&mount_point)) {
+        Some(Err(message)) => format!(" ({})", message),
+        _ => String::new(),
+    };
+    
+    match moved {
+        Ok(_) => FileOpResult {
+            success: true,
+            message: format!(
+                "Saved {} ({} files) to {}{}",
+                archive_name, zipped.affected_count, share.display(), unmount_note
+            ),
+            affected_count: zipped.affected_count,
+        },
+        Err(e) => {
+            let _ = fs::remove_file(&temp_archive);
+            failure(format!("Failed to copy the archive to {}: {}{}", share.display(), e, unmount_note))
+        }
     }
 }
 
-/// Rename a file
-#[uniffi::export]
-pub fn rename_file(path: String, new_name: String) -> FileOpResult {
-    let src_path = std::path::Path::new(&path);
+// The parts of an smb:// URL
+struct SmbShare {
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    user: String,         // "user@" from the URL (any password dropped), or empty
+    server: String,
+    share: String,        // Still percent-encoded, as mount_smbfs expects
+    folder: String,       // Decoded path inside the share, may be empty
+}
+
+impl SmbShare {
+    // For messages; never includes the credentials
+    fn display(&self) -> String {
+        format!("smb://{}/{}", self.server, self.share)
+    }
     
-    if let Some(parent) = src_path.parent() {
-        let new_path = parent.join(&new_name);
-        
-        if new_path.exists() {
-            return FileOpResult {
-                success: false,
-                message: format!("File '{}' already exists", new_name),
-                affected_count: 0,
-            };
-        }
-        
-        match fs::rename(src_path, &new_path) {
-            Ok(_) => FileOpResult {
-                success: true,
-                message: format!("Renamed to '{}'", new_name),
-                affected_count: 1,
-            },
-            Err(e) => FileOpResult {
-                success: false,
-                message: format!("Rename failed: {}", e),
-                affected_count: 0,
-            },
+    // "server/share" in lowercase, decoded, for comparing with mounted shares
+    fn key(&self) -> String {
+        let share = percent_encoding::percent_decode_str(&self.share).decode_utf8_lossy();
+        format!("{}/{}", self.server, share).to_lowercase()
+    }
+}
+
+fn parse_smb_url(url: &str) -> Option<SmbShare> {
+    let rest = url.trim().strip_prefix("smb://")?;
+    let (authority, path) = rest.split_once('/')?;
+    let (user, server) = match authority.rsplit_once('@') {
+        Some((credentials, server)) => {
+            let user = credentials.split_once(':').map_or(credentials, |(user, _password)| user);
+            (format!("{}@", user), server)
         }
+        None => (String::new(), authority),
+    };
+    
+    let mut parts = path.trim_matches('/').splitn(2, '/');
+    let share = parts.next().filter(|s| !s.is_empty())?;
+    let folder = percent_encoding::percent_decode_str(parts.next().unwrap_or("")).decode_utf8_lossy();
+    (!server.is_empty()).then(|| SmbShare {
+        user,
+        server: server.to_string(),
+        share: share.to_string(),
+        folder: folder.to_string(),
+    })
+}
+
+// Where the share is already mounted, if anywhere
+#[cfg(target_os = "macos")]
+fn find_smb_mount(share: &SmbShare) -> Option<PathBuf> {
+    use std::ffi::CStr;
+    
+    let mut mounts: *mut libc::statfs = std::ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut mounts, libc::MNT_NOWAIT) };
+    if count <= 0 || mounts.is_null() {
+        return None;
+    }
+    
+    let key = share.key();
+    let mounts = unsafe { std::slice::from_raw_parts(mounts, count as usize) };
+    mounts.iter().find_map(|m| {
+        let fs_type = unsafe { CStr::from_ptr(m.f_fstypename.as_ptr()) }.to_string_lossy();
+        let from = unsafe { CStr::from_ptr(m.f_mntfromname.as_ptr()) }.to_string_lossy();
+        // f_mntfromname looks like "//user@server/share"
+        let from = from.trim_start_matches('/');
+        let from = from.split_once('@').map_or(from, |(_, rest)| rest);
+        let from = percent_encoding::percent_decode_str(from).decode_utf8_lossy().to_lowercase();
+        (fs_type == "smbfs" && from == key).then(|| {
+            PathBuf::from(unsafe { CStr::from_ptr(m.f_mntonname.as_ptr()) }.to_string_lossy().to_string())
+        })
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn find_smb_mount(share: &SmbShare) -> Option<PathBuf> {
+    let key = share.key();
+    fs::read_to_string("/proc/mounts").ok()?.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let device = unescape_mount_path(fields.next()?);
+        let mount_point = fields.next()?;
+        let fs_type = fields.next()?;
+        let is_smb = matches!(fs_type, "cifs" | "smb3");
+        (is_smb && device.trim_start_matches('/').to_lowercase() == key)
+            .then(|| PathBuf::from(unescape_mount_path(mount_point)))
+    })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn find_smb_mount(_share: &SmbShare) -> Option<PathBuf> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn mount_smb_share(share: &SmbShare) -> Result<PathBuf, String> {
+    let mount_point = env::temp_dir().join(format!("fast-finder-smb-{}", std::process::id()));
+    fs::create_dir_all(&mount_point).map_err(|e| format!("Failed to create mount point: {}", e))?;
+    
+    // -N: never prompt, take the password from the keychain
+    let output = std::process::Command::new("mount_smbfs")
+        .arg("-N")
+        .arg(format!("//{}{}/{}", share.user, share.server, share.share))
+        .arg(&mount_point)
+        .output();
+    
+    let error = match output {
+        Ok(output) if output.status.success() => return Ok(mount_point),
+        Ok(output) => String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        Err(e) => e.to_string(),
+    };
+    let _ = fs::remove_dir(&mount_point);
+    
+    if error.to_lowercase().contains("authentication") {
+        Err(format!(
+            "Authentication failed for {}: check the user name in the URL and the password in the keychain",
+            share.display()
+        ))
     } else {
-        FileOpResult {
-            success: false,
-            message: "Invalid path".to_string(),
-            affected_count: 0,
+        Err(format!("Failed to mount {}: {}", share.display(), error))
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn mount_smb_share(share: &SmbShare) -> Result<PathBuf, String> {
+    Err(format!("{} is not mounted; mount it first and try again", share.display()))
+}
+
+fn unmount_smb_share(mount_point: &Path) -> Result<(), String> {
+    match std::process::Command::new("umount").arg(mount_point).status() {
+        Ok(status) if status.success() => {
+            let _ = fs::remove_dir(mount_point);
+            Ok(())
         }
+        _ => Err(format!("could not unmount {}", mount_point.display())),
     }
 }
 
-/// Create a new folder
+/// Compress files into a ZIP split across `<prefix>.zip.001`, `<prefix>.zip.002`, ...
+/// with each part at most `part_size_bytes`. Concatenating the parts yields a regular ZIP.
 #[uniffi::export]
-pub fn create_folder(path: String) -> FileOpResult {
-    match fs::create_dir_all(&path) {
-        Ok(_) => FileOpResult {
+pub fn compress_files_split(paths: Vec<String>, archive_prefix: String, part_size_bytes: u64) -> FileOpResult {
+    if part_size_bytes == 0 {
+        return FileOpResult {
+            success: false,
+            message: "Part size must be greater than zero".to_string(),
+            affected_count: 0,
+        };
+    }
+    
+    let prefix = archive_prefix.strip_suffix(".zip").unwrap_or(&archive_prefix).to_string();
+    let writer = SplitWriter {
+        prefix,
+        part_size: part_size_bytes,
+        parts: Vec::new(),
+        part_paths: Vec::new(),
+        pos: 0,
+        len: 0,
+    };
+    
+    let mut zip = zip::ZipWriter::new(writer);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    
+    let added = add_files_to_zip(&mut zip, &paths, |_| options, |_, _| {});
+    
+    match zip.finish() {
+        Ok(writer) => FileOpResult {
             success: true,
-            message: format!("Created folder"),
-            affected_count: 1,
+            message: format!(
+                "Compressed {} files into {} parts: {}",
+                added,
+                writer.part_paths.len(),
+                writer.part_paths.join(", ")
+            ),
+            affected_count: added,
         },
         Err(e) => FileOpResult {
             success: false,
-            message: format!("Failed to create folder: {}", e),
+            message: format!("Failed to finalize archive: {}", e),
             affected_count: 0,
         },
     }
 }
 
-/// Compress files into a ZIP archive
-#[uniffi::export]
-pub fn compress_files(paths: Vec<String>, archive_path: String) -> FileOpResult {
-    use std::io::{Read, Write};
+// Seekable writer spanning numbered part files, so ZipWriter can patch
+// local headers that ended up in an earlier part
+struct SplitWriter {
+    prefix: String,
+    part_size: u64,
+    parts: Vec<fs::File>,
+    part_paths: Vec<String>,
+    pos: u64,
+    len: u64,
+}
+
+impl SplitWriter {
+    fn part(&mut self, index: usize) -> io::Result<&mut fs::File> {
+        while self.parts.len() <= index {
+            let path = format!("{}.zip.{:03}", self.prefix, self.parts.len() + 1);
+            let file = fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&path)?;
+            self.parts.push(file);
+            self.part_paths.push(path);
+        }
+        Ok(&mut self.parts[index])
+    }
+}
+
+impl Write for SplitWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        
+        let index = (self.pos / self.part_size) as usize;
+        let offset = self.pos % self.part_size;
+        let n = (self.part_size - offset).min(buf.len() as u64) as usize;
+        
+        let file = self.part(index)?;
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(&buf[..n])?;
+        
+        self.pos += n as u64;
+        self.len = self.len.max(self.pos);
+        Ok(n)
+    }
     
+    fn flush(&mut self) -> io::Result<()> {
+        for part in &mut self.parts {
+            part.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl Seek for SplitWriter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(delta) => self.len.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+        };
+        self.pos = new_pos.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid seek"))?;
+        Ok(self.pos)
+    }
+}
+
+/// Receives progress from `compress_files_with_progress`
+#[uniffi::export(with_foreign)]
+pub trait CompressProgressCallback: Send + Sync {
+    /// A file was added; `compressed_size` is the bytes its entry takes up in the
+    /// archive, local header included. Reported once the entry is finalized, which
+    /// is when the next file starts or the archive is finished.
+    fn on_file_added(&self, name: String, compressed_size: u64);
+    /// Called after each input path, whether or not it could be added
+    fn on_total_progress(&self, files_done: u32, total_files: u32);
+}
+
+/// Compress files into a ZIP archive like `compress_files`, reporting each
+/// added file and the overall progress to `callback`
+#[uniffi::export]
+pub fn compress_files_with_progress(
+    paths: Vec<String>,
+    archive_path: String,
+    callback: Arc<dyn CompressProgressCallback>,
+) -> FileOpResult {
     let file = match fs::File::create(&archive_path) {
         Ok(f) => f,
         Err(e) => return FileOpResult {
@@ -547,29 +6238,30 @@ pub fn compress_files(paths: Vec<String>, archive_path: String) -> FileOpResult
         },
     };
     
-    let mut zip = zip::ZipWriter::new(file);
+    let entry_end = Arc::new(AtomicU64::new(0));
+    let mut zip = zip::ZipWriter::new(EntryEndTracker { inner: file, entry_end: entry_end.clone() });
     let options = zip::write::SimpleFileOptions::default()
         .compression_method(zip::CompressionMethod::Deflated);
     
-    let mut added = 0;
+    let total = paths.len() as u32;
+    // The added entry whose size isn't known yet, and where the one before it ended
+    let mut pending: Option<String> = None;
+    let mut reported_end = 0;
+    let mut report = |name: String| {
+        let end = entry_end.load(Ordering::Relaxed);
+        callback.on_file_added(name, end.saturating_sub(reported_end));
+        reported_end = end;
+    };
     
-    for src in &paths {
-        let src_path = std::path::Path::new(src);
-        if let Some(file_name) = src_path.file_name() {
-            if src_path.is_file() {
-                if let Ok(mut f) = fs::File::open(src_path) {
-                    let mut buffer = Vec::new();
-                    if f.read_to_end(&mut buffer).is_ok() {
-                        if zip.start_file(file_name.to_string_lossy(), options).is_ok() {
-                            if zip.write_all(&buffer).is_ok() {
-                                added += 1;
-                            }
-                        }
-                    }
-                }
+    let added = add_files_to_zip(&mut zip, &paths, |_| options, |i, name| {
+        if let Some(name) = name {
+            // Starting this entry finalized the previous one
+            if let Some(previous) = pending.replace(name.to_string()) {
+                report(previous);
             }
         }
-    }
+        callback.on_total_progress(i as u32 + 1, total);
+    });
     
     if zip.finish().is_err() {
         return FileOpResult {
@@ -578,10 +6270,154 @@ pub fn compress_files(paths: Vec<String>, archive_path: String) -> FileOpResult
             affected_count: 0,
         };
     }
+    if let Some(last) = pending {
+        report(last);
+    }
     
     FileOpResult {
         success: true,
         message: format!("Compressed {} files", added),
         affected_count: added,
     }
-}
\ No newline at end of file
+}
+
+// Passes writes through while publishing where the last finalized entry ended.
+// ZipWriter finalizes an entry by seeking back to rewrite its local header and then
+// seeking to the end of its data, so the target of the latest seek is that end.
+struct EntryEndTracker<W> {
+    inner: W,
+    entry_end: Arc<AtomicU64>,
+}
+
+impl<W: Write> Write for EntryEndTracker<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+    
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for EntryEndTracker<W> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = self.inner.seek(pos)?;
+        self.entry_end.store(new_pos, Ordering::Relaxed);
+        Ok(new_pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn file(len: u64, modified: i64) -> FileMetadata {
+        FileMetadata { len, modified, ..Default::default() }
+    }
+    
+    fn folder() -> FileMetadata {
+        FileMetadata { is_dir: true, ..Default::default() }
+    }
+    
+    fn sample_fs() -> MockFileSystem {
+        MockFileSystem::new(vec![
+            (PathBuf::from("/home/me"), folder()),
+            (PathBuf::from("/home/me/report.pdf"), file(2048, 100)),
+            (PathBuf::from("/home/me/setup.exe"), file(4096, 200)),
+            (PathBuf::from("/home/me/notes"), folder()),
+            (PathBuf::from("/home/me/notes/todo.txt"), file(12, 300)),
+            (PathBuf::from("/home/me/notes/deep/plan.md"), file(64, 400)),
+            (PathBuf::from("/elsewhere/other.pdf"), file(1, 500)),
+        ])
+    }
+    
+    fn walk_sample(config: &WalkConfig, entry_filter: Option<EntryFilter>) -> (Vec<SearchResult>, RebuildStats) {
+        walk_roots_in(&sample_fs(), &["/home/me".to_string()], config, entry_filter, None)
+    }
+    
+    fn names(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.file_name.as_str()).collect()
+    }
+    
+    #[test]
+    fn walk_roots_keeps_indexable_entries_newest_first() {
+        let (results, stats) = walk_sample(&WalkConfig::default(), None);
+        
+        assert_eq!(names(&results), ["plan.md", "todo.txt", "report.pdf", "me", "notes"]);
+        assert_eq!(results[2].file_size, 2048);
+        assert!(results[4].is_folder);
+        assert_eq!(stats.files_scanned, 6);
+        assert_eq!(stats.files_indexed, 5);
+    }
+    
+    #[test]
+    fn walk_roots_respects_max_depth() {
+        let config = WalkConfig { max_depth: 1, ..WalkConfig::default() };
+        let (results, _) = walk_sample(&config, None);
+        
+        assert_eq!(names(&results), ["report.pdf", "me", "notes"]);
+    }
+    
+    #[test]
+    fn walk_roots_applies_entry_filter() {
+        let filter: EntryFilter = Arc::new(|path: &Path, is_folder: bool| !is_folder && path.starts_with("/home/me/notes"));
+        let (results, _) = walk_sample(&WalkConfig::default(), Some(filter));
+        
+        assert_eq!(names(&results), ["plan.md", "todo.txt"]);
+    }
+    
+    #[test]
+    fn walk_roots_skips_missing_roots() {
+        let (results, stats) = walk_roots_in(&sample_fs(), &["/nowhere".to_string()], &WalkConfig::default(), None, None);
+        
+        assert!(results.is_empty());
+        assert_eq!(stats.files_scanned, 0);
+    }
+    
+    #[test]
+    fn mock_metadata_reports_missing_paths() {
+        let error = sample_fs().metadata(Path::new("/home/me/missing.pdf")).unwrap_err();
+        
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+    
+    #[test]
+    fn best_date_prefers_the_later_of_created_and_modified() {
+        let created_later = FileMetadata { modified: 10, created: 20, ..Default::default() };
+        let modified_later = FileMetadata { modified: 30, created: 20, ..Default::default() };
+        
+        assert_eq!(get_best_date(&created_later), (20, "Created"));
+        assert_eq!(get_best_date(&modified_later), (30, "Modified"));
+    }
+    
+    #[test]
+    fn mock_walk_matches_real_walk() {
+        let root = env::temp_dir().join(format!("fast-finder-fs-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+        fs::write(root.join("sub").join("b.md"), "b").unwrap();
+        
+        // Mirror what the real walker sees, then index both the same way
+        let config = WalkConfig::default();
+        let entries = Mutex::new(Vec::new());
+        RealFileSystem.walk(&root, &config, &|entry_result| {
+            if let Ok(entry) = entry_result {
+                let metadata = RealFileSystem.metadata(&entry.path).unwrap();
+                entries.lock().unwrap().push((entry.path, metadata));
+            }
+            true
+        });
+        let roots = [root.to_string_lossy().to_string()];
+        let sorted_names = |(results, _): (Vec<SearchResult>, RebuildStats)| {
+            let mut names: Vec<String> = results.into_iter().map(|r| r.file_name).collect();
+            names.sort();
+            names
+        };
+        let real = sorted_names(walk_roots_in(&RealFileSystem, &roots, &config, None, None));
+        let mock = sorted_names(walk_roots_in(&MockFileSystem::new(entries.into_inner().unwrap()), &roots, &config, None, None));
+        fs::remove_dir_all(&root).unwrap();
+        
+        assert_eq!(real, mock);
+        assert!(real.iter().any(|name| name == "b.md"));
+    }
+}